@@ -1,13 +1,15 @@
-use serde::de::{value::Error as DeError, Visitor};
+use serde::de::Visitor;
 use sqlx::Row as _;
 
 use crate::{
     deserializers::{RowDeserializer, ValueDeserializer},
+    error::Error,
     seq_access::RowSeqAccess,
 };
 
 pub mod mysql;
 pub mod postgres;
+pub mod sqlite;
 
 pub trait Database
 where
@@ -22,22 +24,36 @@ where
     /// columns can be directly deserialized into a struct.
     fn deserialize_json<'a>(
         val_ref: <Self as sqlx::Database>::ValueRef<'a>,
-    ) -> Result<Option<serde_json::Value>, DeError>;
+    ) -> Result<Option<serde_json::Value>, Error>;
 
     /// Some databases have different ways of dealing with sequences, this function will be given a
-    /// type name and using that will determine if it is a sequence that should be handled by the
+    /// type and using that will determine if it is a sequence that should be handled by the
     /// sequence parser. (This is mainly used with postgres so is false by default).
-    fn is_sequence(_type_name: &str) -> bool {
+    fn is_sequence(_type_info: &<Self as sqlx::Database>::TypeInfo) -> bool {
+        false
+    }
+
+    /// Whether values of the type deserialize into structs or tuples by themselves, so that a row
+    /// with just that column can be deserialized into the struct or tuple directly (false by
+    /// default).
+    fn is_compound(_type_info: &<Self as sqlx::Database>::TypeInfo) -> bool {
+        false
+    }
+
+    /// Whether values of the type are maps by themselves, so that a row with just that column
+    /// deserializes into the value's map rather than a map of column name to value (false by
+    /// default).
+    fn is_map(_type_info: &<Self as sqlx::Database>::TypeInfo) -> bool {
         false
     }
 
     /// This function is called when deserializing a sequence from a row.
     ///
     /// This is a separate function as Postgres handles sequences differently to other databases.
-    fn deserialize_seq<'de, 'a, V: Visitor<'de>>(
+    fn deserialize_seq<'de, V: Visitor<'de>>(
         visitor: V,
-        row_deserializer: RowDeserializer<'a, Self>,
-    ) -> Result<V::Value, DeError>
+        row_deserializer: RowDeserializer<'de, Self>,
+    ) -> Result<V::Value, Error>
     where
         usize: sqlx::ColumnIndex<<Self as sqlx::Database>::Row>,
     {
@@ -56,16 +72,17 @@ where
     // fn deserialize_bool<'de, 'a, V: Visitor<'de>>(
     //     deserializer: ValueDeserializer<'a, Self>,
     //     visitor: V,
-    // ) -> Result<V::Value, DeError> {
+    // ) -> Result<V::Value, Error> {
     //     deserializer.deserialize_any(visitor)
     // }
 
     /// Takes a `ValueRef` and a visitor and will parse it depending on it's name.
     ///
-    /// This is the most important function to define for each database.
-    fn deserialize_value<'de, 'a, V: Visitor<'de>>(
-        deserializer: ValueDeserializer<'a, Self>,
+    /// This is the most important function to define for each database. The value lives as long
+    /// as the row, so strings and bytes should be given to the visitor borrowed where possible.
+    fn deserialize_value<'de, V: Visitor<'de>>(
+        deserializer: ValueDeserializer<'de, Self>,
         visitor: V,
         // val_ref: <Self as sqlx::Database>::ValueRef<'a>,
-    ) -> Result<V::Value, DeError>;
+    ) -> Result<V::Value, Error>;
 }
@@ -1,5 +1,9 @@
-use crate::{decode_raw, deserializers::{ValueDeserializer, ValueType}};
-use serde::de::{value::Error as DeError, Deserializer as _, Error, IntoDeserializer as _};
+use crate::{
+    decode_raw,
+    deserializers::{visit_integer_variant, ValueDeserializer, ValueType},
+    error::Error,
+};
+use serde::de::{Deserializer as _, Error as _, IntoDeserializer as _};
 use sqlx::{TypeInfo as _, ValueRef as _};
 
 use super::Database;
@@ -7,7 +11,7 @@ use super::Database;
 impl Database for sqlx::MySql {
     fn deserialize_json<'a>(
         val_ref: <Self as sqlx::Database>::ValueRef<'a>,
-    ) -> Result<Option<serde_json::Value>, serde::de::value::Error> {
+    ) -> Result<Option<serde_json::Value>, Error> {
         let type_info = val_ref.type_info();
         let type_name = type_info.name();
 
@@ -20,17 +24,33 @@ impl Database for sqlx::MySql {
         Ok(Some(value))
     }
 
-    fn deserialize_value<'de, 'a, V: serde::de::Visitor<'de>>(
-        deserializer: ValueDeserializer<'a, Self>,
+    fn deserialize_value<'de, V: serde::de::Visitor<'de>>(
+        deserializer: ValueDeserializer<'de, Self>,
         visitor: V,
-    ) -> Result<V::Value, serde::de::value::Error> {
+    ) -> Result<V::Value, Error> {
         let val_ref = deserializer.value;
 
         let type_info = val_ref.type_info();
         let type_name = type_info.name();
 
-        // Handle enums
-        if deserializer.value_type == ValueType::Enum {
+        // Handle enums, by name or by an integer code
+        if let ValueType::Enum(variants) = deserializer.value_type {
+            match type_name {
+                "TINYINT" | "SMALLINT" | "INT" | "MEDIUMINT" | "BIGINT" => {
+                    let code = decode_raw::<i64, Self>(val_ref)?;
+                    return visit_integer_variant(code, variants, visitor);
+                }
+                "TINYINT UNSIGNED" | "SMALLINT UNSIGNED" | "INT UNSIGNED"
+                | "MEDIUMINT UNSIGNED" | "BIGINT UNSIGNED" => {
+                    let v = decode_raw::<u64, Self>(val_ref)?;
+                    let code = i64::try_from(v).map_err(|_| {
+                        Error::custom(format!("integer `{v}` is too large for a variant"))
+                    })?;
+                    return visit_integer_variant(code, variants, visitor);
+                }
+                _ => {}
+            }
+
             let v = decode_raw::<String, Self>(val_ref)?;
             return visitor.visit_enum(v.into_deserializer());
         }
@@ -59,7 +79,7 @@ impl Database for sqlx::MySql {
                 visitor.visit_i16(v)
             }
             "SMALLINT UNSIGNED" => {
-                let v = decode_raw(val_ref)?;
+                let v = decode_raw::<u16, Self>(val_ref)?;
                 visitor.visit_u16(v)
             }
             "INT" | "MEDIUMINT" | "YEAR" => {
@@ -67,7 +87,7 @@ impl Database for sqlx::MySql {
                 visitor.visit_i32(v)
             }
             "INT UNSIGNED" | "MEDIUMINT UNSIGNED" => {
-                let v = decode_raw(val_ref)?;
+                let v = decode_raw::<u32, Self>(val_ref)?;
                 visitor.visit_u32(v)
             }
             "BIGINT" => {
@@ -75,7 +95,7 @@ impl Database for sqlx::MySql {
                 visitor.visit_i64(v)
             }
             "BIGINT UNSIGNED" => {
-                let v = decode_raw(val_ref)?;
+                let v = decode_raw::<u64, Self>(val_ref)?;
                 visitor.visit_u64(v)
             }
             "FLOAT" => {
@@ -86,12 +106,18 @@ impl Database for sqlx::MySql {
                 let v = decode_raw::<f64, Self>(val_ref)?;
                 visitor.visit_f64(v)
             }
+            // DECIMALs are sent as text, which is given as is unless a float is expected, so
+            // `rust_decimal::Decimal`, `bigdecimal::BigDecimal` and `String` get it exactly
             "DECIMAL" => {
-                let numeric = decode_raw::<rust_decimal::Decimal, Self>(val_ref)?;
-                let num: f64 = numeric
-                    .try_into()
-                    .map_err(|_| DeError::custom("Failed to parse Decimal as f64"))?;
-                visitor.visit_f64(num)
+                let v = decode_raw::<&str, Self>(val_ref)?;
+                if deserializer.value_type == ValueType::Float {
+                    let num: f64 = v
+                        .parse()
+                        .map_err(|_| Error::custom("Failed to parse Decimal as f64"))?;
+                    visitor.visit_f64(num)
+                } else {
+                    visitor.visit_borrowed_str(v)
+                }
             }
             "BOOLEAN" => {
                 let v = decode_raw::<bool, Self>(val_ref)?;
@@ -99,12 +125,12 @@ impl Database for sqlx::MySql {
             }
             "CHAR" | "VARCHAR" | "ENUM" | "SET" | "TINYTEXT" | "TEXT" | "MEDIUMTEXT"
             | "LONGTEXT" => {
-                let v = decode_raw::<String, Self>(val_ref)?;
-                visitor.visit_string(v)
+                let v = decode_raw::<&str, Self>(val_ref)?;
+                visitor.visit_borrowed_str(v)
             }
             "BINARY" | "BIT" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB" | "VARBINARY" => {
                 let v = decode_raw::<&[u8], Self>(val_ref)?;
-                visitor.visit_bytes(v)
+                visitor.visit_borrowed_bytes(v)
             }
             "DATE" => {
                 let v = decode_raw::<chrono::NaiveDate, Self>(val_ref)?;
@@ -123,12 +149,12 @@ impl Database for sqlx::MySql {
                 value
                     .into_deserializer()
                     .deserialize_any(visitor)
-                    .map_err(DeError::custom)
+                    .map_err(Error::json)
             }
             "NULL" => visitor.visit_none(),
             _other => {
-                let as_string = decode_raw::<String, Self>(val_ref)?;
-                visitor.visit_string(as_string)
+                let as_str = decode_raw::<&str, Self>(val_ref)?;
+                visitor.visit_borrowed_str(as_str)
             }
         }
     }
@@ -0,0 +1,316 @@
+use serde::{
+    de::{
+        DeserializeSeed, Error as _, IntoDeserializer, MapAccess, SeqAccess, Unexpected, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use sqlx::postgres::{types::Oid, PgTypeInfo, PgTypeKind};
+
+use crate::error::Error;
+
+use super::value::{builtin_type_info, kind, PgBuf, PgValue};
+
+/// One dimension of an array
+#[derive(Debug, Clone, Copy)]
+struct PgArrayDim {
+    len: usize,
+    /// Index of the first element, Postgres arrays start at 1 unless created otherwise
+    lower_bound: i32,
+}
+
+/// A Postgres array, or a slice of one along its first dimension.
+///
+/// The binary array format is read directly, so every element is deserialized exactly like a
+/// column of the element type would be. Multi-dimensional arrays are sequences of sub-arrays,
+/// i.e. `Vec<Vec<T>>` or `[[T; N]; M]`. Deserializing into a map gives the actual index of
+/// every element as the key, which keeps the offset of arrays that don't start at 1.
+pub(crate) struct PgArray<'a> {
+    /// Just the elements, the header has already been read
+    buf: PgBuf<'a>,
+    dims: Vec<PgArrayDim>,
+    element_type: PgTypeInfo,
+}
+
+impl<'a> PgArray<'a> {
+    /// Whether values of the type are arrays, and should be read with `PgArray`
+    pub(crate) fn is_array(type_info: &PgTypeInfo) -> bool {
+        match kind(type_info) {
+            Some(kind) => matches!(kind, PgTypeKind::Array(_)),
+            None => sqlx::TypeInfo::name(type_info).ends_with("[]"),
+        }
+    }
+
+    pub(crate) fn new(value: PgValue<'a>) -> Result<Self, Error> {
+        // https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/arrayfuncs.c (array_send)
+        let mut buf = value.buf()?;
+        let ndim = buf.i32()?;
+        let _has_nulls = buf.i32()?;
+        let element_oid = Oid(buf.u32()?);
+
+        // The array's own type is preferred as it is also resolved for user defined types
+        let element_type = match kind(&value.type_info) {
+            Some(PgTypeKind::Array(element_type)) => element_type.clone(),
+            _ => builtin_type_info(element_oid)?,
+        };
+
+        // An empty array has no dimensions at all
+        let dims = (0..ndim)
+            .map(|_| {
+                let len = buf.i32()?;
+                let lower_bound = buf.i32()?;
+                let len = usize::try_from(len)
+                    .map_err(|_| Error::custom(format!("invalid array length {len}")))?;
+
+                Ok(PgArrayDim { len, lower_bound })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(PgArray {
+            buf,
+            dims,
+            element_type,
+        })
+    }
+
+    fn elements(self) -> PgArrayElements<'a> {
+        let (remaining, index) = self
+            .dims
+            .first()
+            .map_or((0, 1), |dim| (dim.len, dim.lower_bound));
+
+        PgArrayElements {
+            buf: self.buf,
+            inner_dims: self.dims.get(1..).unwrap_or_default().to_vec(),
+            element_type: self.element_type,
+            index,
+            remaining,
+        }
+    }
+
+    /// Used when the array is asked for something that isn't a sequence, most likely because the
+    /// Rust type has fewer levels of nesting than the array has dimensions
+    fn mismatch<'de, V: Visitor<'de>>(&self, visitor: V) -> Error {
+        let found = format!("{}-dimensional array", self.dims.len());
+        Error::invalid_type(Unexpected::Other(&found), &visitor)
+    }
+}
+
+macro_rules! reject_non_sequences {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                Err(self.mismatch(visitor))
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for PgArray<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(self.elements())
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Fixed size arrays and tuples ignore any elements past their length, so the length is
+    /// checked up front
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let actual = self.dims.first().map_or(0, |dim| dim.len);
+        if actual != len {
+            return Err(Error::invalid_length(actual, &visitor));
+        }
+
+        visitor.visit_seq(self.elements())
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self.elements())
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    reject_non_sequences! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_f32
+        deserialize_f64 deserialize_char deserialize_str deserialize_string deserialize_bytes
+        deserialize_byte_buf deserialize_unit deserialize_identifier
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(self.mismatch(visitor))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(self.mismatch(visitor))
+    }
+
+    forward_to_deserialize_any! {
+        seq struct
+    }
+}
+
+/// Iterates over the first dimension of an array, yielding sub-arrays for multi-dimensional
+/// arrays and values otherwise
+struct PgArrayElements<'a> {
+    buf: PgBuf<'a>,
+    inner_dims: Vec<PgArrayDim>,
+    element_type: PgTypeInfo,
+    /// The Postgres index of the next element
+    index: i32,
+    remaining: usize,
+}
+
+impl<'a> PgArrayElements<'a> {
+    fn next_value(&mut self) -> Result<PgValue<'a>, Error> {
+        // Every element is prefixed by its length, -1 means NULL
+        let len = self.buf.i32()?;
+        let bytes = match usize::try_from(len) {
+            Ok(len) => Some(self.buf.take(len)?),
+            Err(_) => None,
+        };
+
+        Ok(PgValue {
+            bytes,
+            type_info: self.element_type.clone(),
+        })
+    }
+
+    /// Elements are stored flat in row-major order, so a sub-array is the next run of
+    /// elements, as many as the inner dimensions hold
+    fn next_sub_array(&mut self) -> Result<PgArray<'a>, Error> {
+        let count: usize = self.inner_dims.iter().map(|dim| dim.len).product();
+        let start = self.buf.0;
+
+        for _ in 0..count {
+            self.next_value()?;
+        }
+
+        Ok(PgArray {
+            buf: PgBuf(&start[..start.len() - self.buf.0.len()]),
+            dims: self.inner_dims.clone(),
+            element_type: self.element_type.clone(),
+        })
+    }
+
+    fn next_element_seed_inner<U>(&mut self, seed: U) -> Result<U::Value, Error>
+    where
+        U: DeserializeSeed<'a>,
+    {
+        if self.inner_dims.is_empty() {
+            seed.deserialize(self.next_value()?)
+        } else {
+            seed.deserialize(self.next_sub_array()?)
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for PgArrayElements<'de> {
+    type Error = Error;
+
+    fn next_element_seed<U>(&mut self, seed: U) -> Result<Option<U::Value>, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+
+        self.next_element_seed_inner(seed).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> MapAccess<'de> for PgArrayElements<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        seed.deserialize(self.index.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<U>(&mut self, seed: U) -> Result<U::Value, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        self.remaining -= 1;
+        self.index += 1;
+
+        self.next_element_seed_inner(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
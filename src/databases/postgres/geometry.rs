@@ -0,0 +1,259 @@
+use serde::{
+    de::{
+        value::{MapDeserializer, SeqDeserializer},
+        Error as _, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use sqlx::{postgres::PgTypeInfo, TypeInfo as _};
+
+use crate::error::Error;
+
+use super::value::{PgBuf, PgValue};
+
+/// A Postgres geometric value (POINT, LINE, LSEG, BOX, PATH, POLYGON or CIRCLE).
+///
+/// Each deserializes as a map/struct (see `serde_sqlx::types::geo`) or, in the order of its
+/// fields, a tuple:
+/// - POINT: `x`, `y`
+/// - LINE: `a`, `b`, `c` of the equation `ax + by + c = 0`
+/// - LSEG: `start`, `end`
+/// - BOX: `high`, `low`, its upper right and lower left corners
+/// - PATH: `closed`, `points`
+/// - POLYGON: `points`
+/// - CIRCLE: `center`, `radius`
+///
+/// Points deserialize the same way, and types made of points also deserialize as a sequence of
+/// them, e.g. a polygon into `Vec<(f64, f64)>`.
+pub(crate) struct PgGeometry(Geo);
+
+impl PgGeometry {
+    /// Whether values of the type are geometric, and should be read with `PgGeometry`
+    pub(crate) fn is_geometry(type_info: &PgTypeInfo) -> bool {
+        matches!(
+            type_info.name(),
+            "POINT" | "LINE" | "LSEG" | "BOX" | "PATH" | "POLYGON" | "CIRCLE"
+        )
+    }
+
+    pub(crate) fn new(value: PgValue) -> Result<Self, Error> {
+        // https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/geo_ops.c
+        let mut buf = value.buf()?;
+
+        let geo = match value.type_info.name() {
+            "POINT" => point(&mut buf)?,
+            "LINE" => Geo::Shape(vec![
+                ("a", Geo::Number(buf.f64()?)),
+                ("b", Geo::Number(buf.f64()?)),
+                ("c", Geo::Number(buf.f64()?)),
+            ]),
+            "LSEG" => Geo::Shape(vec![("start", point(&mut buf)?), ("end", point(&mut buf)?)]),
+            "BOX" => Geo::Shape(vec![("high", point(&mut buf)?), ("low", point(&mut buf)?)]),
+            "PATH" => {
+                let closed = buf.take(1)?[0] != 0;
+                Geo::Shape(vec![
+                    ("closed", Geo::Flag(closed)),
+                    ("points", points(&mut buf)?),
+                ])
+            }
+            "POLYGON" => Geo::Shape(vec![("points", points(&mut buf)?)]),
+            "CIRCLE" => Geo::Shape(vec![
+                ("center", point(&mut buf)?),
+                ("radius", Geo::Number(buf.f64()?)),
+            ]),
+            other => return Err(Error::custom(format!("unknown geometric type {other}"))),
+        };
+
+        Ok(PgGeometry(geo))
+    }
+}
+
+fn point(buf: &mut PgBuf) -> Result<Geo, Error> {
+    Ok(Geo::Shape(vec![
+        ("x", Geo::Number(buf.f64()?)),
+        ("y", Geo::Number(buf.f64()?)),
+    ]))
+}
+
+fn points(buf: &mut PgBuf) -> Result<Geo, Error> {
+    let len = buf.i32()?;
+    let len = usize::try_from(len)
+        .map_err(|_| Error::custom(format!("invalid number of points {len}")))?;
+
+    let points = (0..len).map(|_| point(buf)).collect::<Result<_, _>>()?;
+    Ok(Geo::Points(points))
+}
+
+/// A geometric value or one of its parts
+enum Geo {
+    Number(f64),
+    Flag(bool),
+    Points(Vec<Geo>),
+    /// Named fields, in the order they are sent
+    Shape(Vec<(&'static str, Geo)>),
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Geo {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for Geo {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Geo::Number(number) => visitor.visit_f64(number),
+            Geo::Flag(flag) => visitor.visit_bool(flag),
+            Geo::Points(points) => visitor.visit_seq(SeqDeserializer::new(points.into_iter())),
+            Geo::Shape(fields) => visitor.visit_map(MapDeserializer::new(fields.into_iter())),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// The points of a shape made of them, otherwise its fields in order
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let Geo::Shape(fields) = self else {
+            return self.deserialize_any(visitor);
+        };
+
+        if let Some(index) = fields.iter().position(|(name, _)| *name == "points") {
+            let (_, points) = fields.into_iter().nth(index).expect("found the points");
+            return points.deserialize_any(visitor);
+        }
+
+        let values = fields.into_iter().map(|(_, value)| value);
+        visitor.visit_seq(SeqDeserializer::new(values))
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            // A tuple of every field, unless the shape is just points
+            Geo::Shape(fields) if fields.len() == len => {
+                let values = fields.into_iter().map(|(_, value)| value);
+                visitor.visit_seq(SeqDeserializer::new(values))
+            }
+            geo => geo.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct map struct enum identifier
+    }
+}
+
+impl<'de> Deserializer<'de> for PgGeometry {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct map struct enum identifier
+    }
+}
@@ -0,0 +1,120 @@
+use serde::{
+    de::{value::BorrowedStrDeserializer, DeserializeSeed, Error as _, MapAccess, Visitor},
+    forward_to_deserialize_any, Deserializer,
+};
+use sqlx::{postgres::PgTypeInfo, Postgres, Type};
+
+use crate::error::Error;
+
+use super::value::{is_extension_type, PgBuf, PgValue};
+
+/// A value of the `hstore` extension, a set of text keys with nullable text values.
+///
+/// Hstores deserialize as maps, so they work with `HashMap<String, Option<String>>`, `BTreeMap`
+/// and structs keyed by hstore keys. Values are TEXT, so they deserialize like a TEXT column.
+pub(crate) struct PgHstore<'a> {
+    buf: PgBuf<'a>,
+    len: usize,
+}
+
+impl<'a> PgHstore<'a> {
+    /// Whether values of the type are hstores, and should be read with `PgHstore`
+    pub(crate) fn is_hstore(type_info: &PgTypeInfo) -> bool {
+        is_extension_type(type_info, &["hstore"])
+    }
+
+    pub(crate) fn new(value: PgValue<'a>) -> Result<Self, Error> {
+        // https://github.com/postgres/postgres/blob/master/contrib/hstore/hstore_io.c (hstore_send)
+        let mut buf = value.buf()?;
+        let len = buf.i32()?;
+        let len = usize::try_from(len)
+            .map_err(|_| Error::custom(format!("invalid hstore length {len}")))?;
+
+        Ok(PgHstore { buf, len })
+    }
+}
+
+impl<'de> Deserializer<'de> for PgHstore<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct enum identifier
+    }
+}
+
+impl<'de> MapAccess<'de> for PgHstore<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+
+        // Keys are never NULL
+        let len = self.buf.i32()?;
+        let len = usize::try_from(len)
+            .map_err(|_| Error::custom(format!("invalid hstore key length {len}")))?;
+        let key = std::str::from_utf8(self.buf.take(len)?).map_err(Error::custom)?;
+
+        seed.deserialize(BorrowedStrDeserializer::new(key))
+            .map(Some)
+    }
+
+    fn next_value_seed<U>(&mut self, seed: U) -> Result<U::Value, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        // A length of -1 means NULL
+        let len = self.buf.i32()?;
+        let bytes = match usize::try_from(len) {
+            Ok(len) => Some(self.buf.take(len)?),
+            Err(_) => None,
+        };
+
+        seed.deserialize(PgValue {
+            bytes,
+            type_info: <String as Type<Postgres>>::type_info(),
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
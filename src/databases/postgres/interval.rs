@@ -0,0 +1,227 @@
+use std::fmt::Write as _;
+
+use serde::{
+    de::{
+        value::{MapDeserializer, SeqDeserializer},
+        Error as _, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use sqlx::{postgres::PgTypeInfo, TypeInfo as _};
+
+use crate::error::Error;
+
+use super::value::PgValue;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+
+/// The name and fields `std::time::Duration` deserializes as
+const STD_DURATION: (&str, [&str; 2]) = ("Duration", ["secs", "nanos"]);
+
+/// A Postgres INTERVAL, which is made of months, days and microseconds that don't convert into
+/// each other.
+///
+/// Intervals deserialize as:
+/// - maps/structs of `months`, `days` and `microseconds` (see `serde_sqlx::types::Interval`)
+/// - ISO-8601 duration strings, e.g. `P1Y2M3DT4H5M6.5S`
+/// - `(seconds, nanoseconds)` tuples, which is what `chrono::Duration` and `time::Duration`
+///   deserialize from, and `std::time::Duration`
+///
+/// A day is taken as 24 hours for fixed length durations, but months have no fixed length, so
+/// an interval with months can't become one.
+pub(crate) struct PgIntervalValue {
+    months: i32,
+    days: i32,
+    microseconds: i64,
+}
+
+impl PgIntervalValue {
+    /// Whether values of the type are intervals, and should be read with `PgIntervalValue`
+    pub(crate) fn is_interval(type_info: &PgTypeInfo) -> bool {
+        type_info.name() == "INTERVAL"
+    }
+
+    pub(crate) fn new(value: PgValue) -> Result<Self, Error> {
+        let mut buf = value.buf()?;
+
+        Ok(PgIntervalValue {
+            microseconds: buf.i64()?,
+            days: buf.i32()?,
+            months: buf.i32()?,
+        })
+    }
+
+    /// The interval as whole seconds and the nanoseconds after them, always positive
+    fn duration(&self) -> Result<(i64, i64), Error> {
+        if self.months != 0 {
+            return Err(Error::custom(format!(
+                "INTERVAL `{}` has months, which have no fixed length",
+                self.iso_8601()
+            )));
+        }
+
+        let microseconds = i64::from(self.days)
+            .checked_mul(MICROS_PER_DAY)
+            .and_then(|days| days.checked_add(self.microseconds))
+            .ok_or_else(|| {
+                Error::custom(format!(
+                    "INTERVAL `{}` is out of range for a duration",
+                    self.iso_8601()
+                ))
+            })?;
+
+        Ok((
+            microseconds.div_euclid(MICROS_PER_SECOND),
+            microseconds.rem_euclid(MICROS_PER_SECOND) * 1000,
+        ))
+    }
+
+    /// The format of Postgres' `intervalstyle = iso_8601`, where each part keeps its own sign
+    fn iso_8601(&self) -> String {
+        if self.months == 0 && self.days == 0 && self.microseconds == 0 {
+            return "PT0S".to_owned();
+        }
+
+        let mut iso = "P".to_owned();
+        let (years, months) = (self.months / 12, self.months % 12);
+        for (amount, unit) in [(years, 'Y'), (months, 'M'), (self.days, 'D')] {
+            if amount != 0 {
+                let _ = write!(iso, "{amount}{unit}");
+            }
+        }
+
+        if self.microseconds == 0 {
+            return iso;
+        }
+
+        iso.push('T');
+        let hours = self.microseconds / MICROS_PER_HOUR;
+        let minutes = self.microseconds % MICROS_PER_HOUR / MICROS_PER_MINUTE;
+        let seconds = self.microseconds % MICROS_PER_MINUTE;
+        for (amount, unit) in [(hours, 'H'), (minutes, 'M')] {
+            if amount != 0 {
+                let _ = write!(iso, "{amount}{unit}");
+            }
+        }
+
+        if seconds != 0 {
+            let sign = if seconds < 0 { "-" } else { "" };
+            let seconds = seconds.unsigned_abs();
+            let (whole, fraction) = (seconds / 1_000_000, seconds % 1_000_000);
+            let _ = write!(iso, "{sign}{whole}");
+            if fraction != 0 {
+                let fraction = format!("{fraction:06}");
+                let _ = write!(iso, ".{}", fraction.trim_end_matches('0'));
+            }
+            iso.push('S');
+        }
+
+        iso
+    }
+}
+
+impl<'de> Deserializer<'de> for PgIntervalValue {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.iso_8601())
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if len != 2 {
+            return Err(Error::invalid_length(2, &visitor));
+        }
+
+        let (seconds, nanoseconds) = self.duration()?;
+        visitor.visit_seq(SeqDeserializer::new([seconds, nanoseconds].into_iter()))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let fields = [
+            ("months", i64::from(self.months)),
+            ("days", i64::from(self.days)),
+            ("microseconds", self.microseconds),
+        ];
+
+        visitor.visit_map(MapDeserializer::new(fields.into_iter()))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if (name, fields) != (STD_DURATION.0, &STD_DURATION.1[..]) {
+            return self.deserialize_map(visitor);
+        }
+
+        let (seconds, nanoseconds) = self.duration()?;
+        if seconds < 0 {
+            return Err(Error::custom(format!(
+                "INTERVAL `{}` is negative, which a std::time::Duration can't be",
+                self.iso_8601()
+            )));
+        }
+
+        let fields = STD_DURATION.1.into_iter().zip([seconds, nanoseconds]);
+        visitor.visit_map(MapDeserializer::new(fields))
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq enum identifier
+    }
+}
@@ -1,5 +1,5 @@
 use serde::{
-    de::{self, value::Error as DeError, Error as _, IntoDeserializer},
+    de::{self, Error as _, IntoDeserializer},
     forward_to_deserialize_any, Deserializer,
 };
 use serde_json::Value;
@@ -8,36 +8,42 @@ use sqlx::{
     TypeInfo as _, ValueRef as _,
 };
 
+use crate::error::Error;
+
 /// Decodes Postgres' JSON or JSONB into serde_json::Value
 #[derive(Debug)]
 pub(crate) struct PgJson(pub(crate) serde_json::Value);
 
-impl<'a> sqlx::Decode<'a, sqlx::Postgres> for PgJson {
-    fn decode(value: PgValueRef<'a>) -> Result<Self, sqlx::error::BoxDynError> {
-        let is_jsonb = match value.type_info().name() {
-            "JSON" => false,
-            "JSONB" => true,
-            other => unreachable!("Got {other} in PgJson"),
-        };
-
-        let mut bytes = value.as_bytes()?;
-
+impl PgJson {
+    pub(crate) fn from_bytes(mut bytes: &[u8], is_jsonb: bool) -> Result<Self, Error> {
         // For JSONB, the first byte is a version (should be 1)
         if is_jsonb {
             if bytes.is_empty() || bytes[0] != 1 {
-                return Err("invalid JSONB header".into());
+                return Err(Error::custom("invalid JSONB header"));
             }
 
             // Skip the version byte
             bytes = &bytes[1..]
         };
 
-        let value = serde_json::from_slice(bytes)?;
+        let value = serde_json::from_slice(bytes).map_err(Error::json)?;
 
         Ok(PgJson(value))
     }
 }
 
+impl<'a> sqlx::Decode<'a, sqlx::Postgres> for PgJson {
+    fn decode(value: PgValueRef<'a>) -> Result<Self, sqlx::error::BoxDynError> {
+        let is_jsonb = match value.type_info().name() {
+            "JSON" => false,
+            "JSONB" => true,
+            other => unreachable!("Got {other} in PgJson"),
+        };
+
+        Ok(PgJson::from_bytes(value.as_bytes()?, is_jsonb)?)
+    }
+}
+
 impl sqlx::Type<sqlx::Postgres> for PgJson {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::with_name("JSON")
@@ -49,14 +55,14 @@ pub struct PgJsonDeserializer {
 }
 
 impl<'de> Deserializer<'de> for PgJsonDeserializer {
-    type Error = DeError;
+    type Error = Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
         // Delegate to serde_json::Value's own Deserializer
-        self.value.deserialize_any(visitor).map_err(DeError::custom)
+        self.value.deserialize_any(visitor).map_err(Error::json)
     }
 
     forward_to_deserialize_any! {
@@ -66,7 +72,7 @@ impl<'de> Deserializer<'de> for PgJsonDeserializer {
     }
 }
 
-impl<'de> IntoDeserializer<'de> for PgJson {
+impl<'de> IntoDeserializer<'de, Error> for PgJson {
     type Deserializer = PgJsonDeserializer;
 
     fn into_deserializer(self) -> Self::Deserializer {
@@ -1,22 +1,49 @@
+use geometry::PgGeometry;
+use hstore::PgHstore;
+use interval::PgIntervalValue;
 use json::PgJson;
-use seq::PgArraySeqAccess;
+use network::{PgInet, PgMacAddr};
+use range::PgRangeValue;
+use record::PgRecord;
 use serde::{
-    de::{value::Error as DeError, Error as _, IntoDeserializer, Visitor},
+    de::{value::BorrowedStrDeserializer, Error as _, IntoDeserializer as _, Visitor},
     Deserializer,
 };
-use sqlx::{Row as _, TypeInfo as _, ValueRef as _};
-
+use sqlx::{
+    postgres::{types::Oid, PgTypeInfo, PgTypeKind, PgValueFormat, PgValueRef},
+    Row as _, TypeInfo as _, ValueRef as _,
+};
+use text_array::PgTextArray;
+use value::{PgNested, PgValue};
+use vector::PgVector;
+
+mod array;
+mod geometry;
+mod hstore;
+mod interval;
 mod json;
-mod seq;
-
-use crate::{decode_raw, deserializers::ValueDeserializer, seq_access::RowSeqAccess};
+mod network;
+#[cfg(feature = "postgis")]
+mod postgis;
+mod range;
+mod record;
+mod text_array;
+mod value;
+mod vector;
+
+use crate::{
+    decode_raw,
+    deserializers::{visit_integer_variant, ValueDeserializer, ValueType},
+    error::Error,
+    seq_access::RowSeqAccess,
+};
 
 use super::Database;
 
 impl Database for sqlx::Postgres {
     fn deserialize_json<'a>(
         val_ref: <Self as sqlx::Database>::ValueRef<'a>,
-    ) -> Result<Option<serde_json::Value>, DeError> {
+    ) -> Result<Option<serde_json::Value>, Error> {
         let type_info = val_ref.type_info();
         let type_name = type_info.name();
 
@@ -24,144 +51,172 @@ impl Database for sqlx::Postgres {
             return Ok(None);
         }
 
-        let value = decode_raw::<PgJson, sqlx::Postgres>(val_ref)
-            .map_err(|err| DeError::custom(format!("Failed to decode JSON/JSONB: {err}")))?;
+        let value = decode_raw::<PgJson, sqlx::Postgres>(val_ref)?;
 
         Ok(Some(value.0))
     }
 
-    fn is_sequence(type_name: &str) -> bool {
-        type_name.ends_with("[]")
+    fn is_sequence(type_info: &PgTypeInfo) -> bool {
+        // Arrays of user defined types are named after the element with a leading underscore,
+        // e.g. `_mood`, so the kind has to be checked rather than the name
+        matches!(type_info.kind(), PgTypeKind::Array(_))
     }
 
-    fn deserialize_seq<'de, 'a, V: Visitor<'de>>(
+    fn is_compound(type_info: &PgTypeInfo) -> bool {
+        PgRangeValue::is_range(type_info)
+            || PgRecord::is_record(type_info)
+            || PgIntervalValue::is_interval(type_info)
+            || PgHstore::is_hstore(type_info)
+            || PgInet::is_inet(type_info)
+            || PgMacAddr::is_mac_addr(type_info)
+            || PgGeometry::is_geometry(type_info)
+            || PgVector::is_vector(type_info)
+            || is_postgis(type_info)
+    }
+
+    fn is_map(type_info: &PgTypeInfo) -> bool {
+        PgHstore::is_hstore(type_info)
+    }
+
+    fn deserialize_seq<'de, V: Visitor<'de>>(
         visitor: V,
-        row_deserializer: crate::deserializers::RowDeserializer<'a, Self>,
-    ) -> Result<V::Value, DeError>
+        row_deserializer: crate::deserializers::RowDeserializer<'de, Self>,
+    ) -> Result<V::Value, Error>
     where
         usize: sqlx::ColumnIndex<<Self as sqlx::Database>::Row>,
     {
         let raw_value = row_deserializer
             .row
             .try_get_raw(row_deserializer.index)
-            .map_err(DeError::custom)?;
+            .map_err(Error::custom)?;
+
         let type_info = raw_value.type_info();
-        let type_name = type_info.name();
+        let is_nested = PgNested::is_nested(&type_info);
+
+        // With more than one column the sequence is the row itself, e.g. a tuple, and any array
+        // or record column is just one of its elements
+        if row_deserializer.row.columns().len() > 1 || !is_nested {
+            let num_cols = row_deserializer.row.columns().len();
+            let seq_access = RowSeqAccess {
+                deserializer: row_deserializer,
+                num_cols,
+            };
+
+            return visitor.visit_seq(seq_access);
+        }
 
-        match type_name {
-            "TEXT[]" | "VARCHAR[]" => {
-                let seq_access = PgArraySeqAccess::<String>::new(raw_value)?;
-                visitor.visit_seq(seq_access)
-            }
-            "INT4[]" => {
-                let seq_access = PgArraySeqAccess::<i32>::new(raw_value)?;
-                visitor.visit_seq(seq_access)
-            }
-            "JSON[]" | "JSONB[]" => {
-                let seq_access = PgArraySeqAccess::<PgJson>::new(raw_value)?;
-                visitor.visit_seq(seq_access)
-            }
-            "BOOL[]" => {
-                let seq_access = PgArraySeqAccess::<bool>::new(raw_value)?;
-                visitor.visit_seq(seq_access)
-            }
-            _ => {
-                let num_cols = row_deserializer.row.columns().len();
-                let seq_access = RowSeqAccess {
-                    deserializer: row_deserializer,
-                    num_cols,
-                };
-
-                visitor.visit_seq(seq_access)
-            }
+        let column = &row_deserializer.row.columns()[row_deserializer.index];
+
+        if raw_value.format() == PgValueFormat::Text {
+            return deserialize_text(raw_value, ValueType::Seq, visitor)
+                .map_err(|err| err.in_column(column));
         }
+
+        PgValue::new(raw_value)
+            .and_then(|value| value.deserialize_seq(visitor))
+            .map_err(|err| err.in_column(column))
     }
 
-    fn deserialize_value<'de, 'a, V: Visitor<'de>>(
-        deserializer: ValueDeserializer<'a, Self>,
+    fn deserialize_value<'de, V: Visitor<'de>>(
+        deserializer: ValueDeserializer<'de, Self>,
         visitor: V,
-    ) -> Result<V::Value, DeError> {
+    ) -> Result<V::Value, Error> {
         let val_ref = deserializer.value;
-        let type_info = val_ref.type_info();
-        let type_name = type_info.name();
 
-        match type_name {
-            "FLOAT4" => {
-                let v = decode_raw::<f32, Self>(val_ref)?;
-                visitor.visit_f32(v)
-            }
-            "FLOAT8" => {
-                let v = decode_raw::<f64, Self>(val_ref)?;
-                visitor.visit_f64(v)
-            }
-            "NUMERIC" => {
-                let numeric = decode_raw::<rust_decimal::Decimal, Self>(val_ref)?;
-
-                let num: f64 = numeric
-                    .try_into()
-                    .map_err(|_| DeError::custom("Failed to parse Decimal as f64"))?;
-
-                visitor.visit_f64(num)
-            }
-            "INT8" => {
-                let v = decode_raw::<i64, Self>(val_ref)?;
-                visitor.visit_i64(v)
-            }
-            "INT4" => {
-                let v = decode_raw::<i32, Self>(val_ref)?;
-                visitor.visit_i32(v)
-            }
-            "INT2" => {
-                let v = decode_raw::<i16, Self>(val_ref)?;
-                visitor.visit_i16(v)
-            }
-            "BOOL" => {
-                let v = decode_raw::<bool, Self>(val_ref)?;
-                visitor.visit_bool(v)
-            }
-            "DATE" => {
-                let date = decode_raw::<chrono::NaiveDate, Self>(val_ref)?;
-                visitor.visit_string(date.to_string())
-            }
-            "TIME" | "TIMETZ" => {
-                let time = decode_raw::<chrono::NaiveTime, Self>(val_ref)?;
-                visitor.visit_string(time.to_string())
-            }
-            "TIMESTAMP" | "TIMESTAMPTZ" => {
-                let ts = decode_raw::<chrono::DateTime<chrono::FixedOffset>, Self>(val_ref)?;
-                visitor.visit_string(ts.to_rfc3339())
-            }
-            "UUID" => {
-                let uuid = decode_raw::<uuid::Uuid, Self>(val_ref)?;
-                visitor.visit_string(uuid.to_string())
-            }
-            "BYTEA" => {
-                let bytes = decode_raw::<&[u8], Self>(val_ref)?;
-                visitor.visit_bytes(bytes)
-            }
-            "INTERVAL" => {
-                let pg_interval = decode_raw::<sqlx::postgres::types::PgInterval, Self>(val_ref)?;
-                let secs = pg_interval.microseconds / 1_000_000;
-                let nanos = (pg_interval.microseconds % 1_000_000) * 1000;
-                let days_duration = chrono::Duration::days(pg_interval.days as i64);
-                let duration = chrono::Duration::seconds(secs)
-                    + chrono::Duration::nanoseconds(nanos)
-                    + days_duration;
-                visitor.visit_string(duration.to_string())
-            }
-            "CHAR" | "TEXT" => {
-                let s = decode_raw::<String, Self>(val_ref)?;
-                visitor.visit_string(s)
-            }
-            "JSON" | "JSONB" => {
-                let value = decode_raw::<PgJson, Self>(val_ref)?;
-
-                value.into_deserializer().deserialize_any(visitor)
-            }
-            _other => {
-                let as_string = decode_raw::<String, Self>(val_ref)?;
-                visitor.visit_string(as_string)
-            }
+        // Only the simple query protocol (e.g. `sqlx::raw_sql`) sends values as text
+        if val_ref.format() == PgValueFormat::Text {
+            return deserialize_text(val_ref, deserializer.value_type, visitor);
         }
+
+        let value = PgValue::new(val_ref)?;
+
+        match deserializer.value_type {
+            ValueType::Map => value.deserialize_map(visitor),
+            ValueType::Seq => value.deserialize_seq(visitor),
+            ValueType::Tuple(len) => value.deserialize_tuple(len, visitor),
+            ValueType::Float => value.deserialize_f64(visitor),
+            ValueType::Enum(variants) => value.deserialize_enum("", variants, visitor),
+            ValueType::Struct(name, fields) => value.deserialize_struct(name, fields, visitor),
+            _ => value.deserialize_any(visitor),
+        }
+    }
+}
+
+/// Visits a value sent as text, which sqlx decodes for the basic types. Arrays are parsed into
+/// their elements, see `PgTextArray`. Everything else is given as the text Postgres shows for it,
+/// so only scalar types are supported over the simple query protocol: compound types such as
+/// INTERVAL, ranges, geometric types and composites are strings like `1 day` or `(1,2)`, which
+/// `serde_sqlx::types::Interval`, tuples and structs reject
+fn deserialize_text<'de, V: Visitor<'de>>(
+    val_ref: PgValueRef<'de>,
+    value_type: ValueType,
+    visitor: V,
+) -> Result<V::Value, Error> {
+    let type_info = val_ref.type_info();
+
+    if matches!(type_info.kind(), PgTypeKind::Array(_)) {
+        let type_info = type_info.into_owned();
+        let text = decode_raw::<&str, sqlx::Postgres>(val_ref)?;
+        let array = PgTextArray::new(text, &type_info)?;
+
+        return match value_type {
+            ValueType::Map => array.deserialize_map(visitor),
+            ValueType::Tuple(len) => array.deserialize_tuple(len, visitor),
+            _ => array.deserialize_any(visitor),
+        };
+    }
+
+    match (type_info.name(), value_type) {
+        ("INT2" | "INT4" | "INT8", ValueType::Enum(variants)) => {
+            let code = decode_raw::<i64, sqlx::Postgres>(val_ref)?;
+            visit_integer_variant(code, variants, visitor)
+        }
+        ("INT8", _) => visitor.visit_i64(decode_raw::<i64, sqlx::Postgres>(val_ref)?),
+        ("INT4", _) => visitor.visit_i32(decode_raw::<i32, sqlx::Postgres>(val_ref)?),
+        ("INT2", _) => visitor.visit_i16(decode_raw::<i16, sqlx::Postgres>(val_ref)?),
+        ("OID", _) => visitor.visit_u32(decode_raw::<Oid, sqlx::Postgres>(val_ref)?.0),
+        ("FLOAT4", _) => visitor.visit_f32(decode_raw::<f32, sqlx::Postgres>(val_ref)?),
+        ("FLOAT8", _) => visitor.visit_f64(decode_raw::<f64, sqlx::Postgres>(val_ref)?),
+        ("BOOL", _) => visitor.visit_bool(decode_raw::<bool, sqlx::Postgres>(val_ref)?),
+        // Exact unless a float is expected, as in the binary format
+        ("NUMERIC", ValueType::Float) => {
+            let s = decode_raw::<&str, sqlx::Postgres>(val_ref)?;
+            let num: f64 = s
+                .parse()
+                .map_err(|_| Error::custom("Failed to parse NUMERIC as f64"))?;
+            visitor.visit_f64(num)
+        }
+        ("TIMESTAMP", _) => {
+            let ts = decode_raw::<chrono::NaiveDateTime, sqlx::Postgres>(val_ref)?;
+            visitor.visit_string(ts.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+        }
+        ("TIMESTAMPTZ", _) => {
+            let ts = decode_raw::<chrono::DateTime<chrono::Utc>, sqlx::Postgres>(val_ref)?;
+            visitor.visit_string(ts.fixed_offset().to_rfc3339())
+        }
+        ("BYTEA", _) => visitor.visit_byte_buf(decode_raw::<Vec<u8>, sqlx::Postgres>(val_ref)?),
+        // JSONB as text has no version byte
+        ("JSON" | "JSONB", _) => {
+            let s = decode_raw::<&str, sqlx::Postgres>(val_ref)?;
+            PgJson::from_bytes(s.as_bytes(), false)?
+                .into_deserializer()
+                .deserialize_any(visitor)
+        }
+        (_, ValueType::Enum(_)) => {
+            let s = decode_raw::<&str, sqlx::Postgres>(val_ref)?;
+            visitor.visit_enum(BorrowedStrDeserializer::new(s))
+        }
+        _ => visitor.visit_borrowed_str(decode_raw::<&str, sqlx::Postgres>(val_ref)?),
     }
 }
+
+/// PostGIS values are only read, as GeoJSON, with the `postgis` feature
+#[cfg(feature = "postgis")]
+fn is_postgis(type_info: &PgTypeInfo) -> bool {
+    postgis::is_postgis(type_info)
+}
+
+#[cfg(not(feature = "postgis"))]
+fn is_postgis(_type_info: &PgTypeInfo) -> bool {
+    false
+}
@@ -0,0 +1,312 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use serde::{
+    de::{
+        value::{MapDeserializer, SeqDeserializer},
+        Error as _, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use sqlx::{postgres::PgTypeInfo, TypeInfo as _};
+
+use crate::error::Error;
+
+use super::value::PgValue;
+
+/// `PGSQL_AF_INET6` in Postgres, IPv4 is `PGSQL_AF_INET` (2)
+const PGSQL_AF_INET6: u8 = 3;
+
+/// A Postgres INET or CIDR, an IP address with the length of its network prefix.
+///
+/// Addresses deserialize as the text Postgres shows for them, e.g. `192.168.0.1` or
+/// `10.0.0.0/8`, which `std::net::IpAddr` (for hosts) and `ipnetwork::IpNetwork` parse. They
+/// also deserialize as `(address, prefix)` tuples, or maps/structs of `address` and `prefix`.
+pub(crate) struct PgInet {
+    address: IpAddr,
+    prefix: u8,
+    is_cidr: bool,
+}
+
+impl PgInet {
+    /// Whether values of the type are INET or CIDR, and should be read with `PgInet`
+    pub(crate) fn is_inet(type_info: &PgTypeInfo) -> bool {
+        matches!(type_info.name(), "INET" | "CIDR")
+    }
+
+    pub(crate) fn new(value: PgValue) -> Result<Self, Error> {
+        // https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/network.c (network_send)
+        let mut buf = value.buf()?;
+        let [family, prefix, is_cidr, len] = buf.take(4)?.try_into().expect("took 4 bytes");
+        let bytes = buf.take(usize::from(len))?;
+
+        let address = match (family, <[u8; 16]>::try_from(bytes)) {
+            (PGSQL_AF_INET6, Ok(octets)) => IpAddr::V6(Ipv6Addr::from(octets)),
+            (PGSQL_AF_INET6, Err(_)) => return Err(Error::custom("invalid IPv6 address length")),
+            _ => {
+                let octets: [u8; 4] = bytes
+                    .try_into()
+                    .map_err(|_| Error::custom("invalid IPv4 address length"))?;
+                IpAddr::V4(Ipv4Addr::from(octets))
+            }
+        };
+
+        Ok(PgInet {
+            address,
+            prefix,
+            is_cidr: is_cidr != 0,
+        })
+    }
+
+    /// Like Postgres, the prefix is left out of an INET that is a single host
+    fn text(&self) -> String {
+        let host_prefix = if self.address.is_ipv4() { 32 } else { 128 };
+        if self.is_cidr || self.prefix != host_prefix {
+            format!("{}/{}", self.address, self.prefix)
+        } else {
+            self.address.to_string()
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for PgInet {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.text())
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if len != 2 {
+            return Err(Error::invalid_length(2, &visitor));
+        }
+
+        let parts = [
+            InetPart::Address(self.address.to_string()),
+            InetPart::Prefix(self.prefix),
+        ];
+        visitor.visit_seq(SeqDeserializer::new(parts.into_iter()))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let fields = [
+            ("address", InetPart::Address(self.address.to_string())),
+            ("prefix", InetPart::Prefix(self.prefix)),
+        ];
+
+        visitor.visit_map(MapDeserializer::new(fields.into_iter()))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq enum identifier
+    }
+}
+
+/// The `address` or `prefix` of an INET or CIDR, as a tuple element or map value
+enum InetPart {
+    Address(String),
+    Prefix(u8),
+}
+
+impl<'de> IntoDeserializer<'de, Error> for InetPart {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for InetPart {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            InetPart::Address(address) => visitor.visit_string(address),
+            InetPart::Prefix(prefix) => visitor.visit_u8(prefix),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// A Postgres MACADDR or MACADDR8, a hardware address of 6 or 8 bytes.
+///
+/// Addresses deserialize as the text Postgres shows for them, e.g. `08:00:2b:01:02:03`, or as
+/// their bytes, e.g. `[u8; 6]` or `Vec<u8>`.
+pub(crate) struct PgMacAddr<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> PgMacAddr<'a> {
+    /// Whether values of the type are MACADDR or MACADDR8, and should be read with `PgMacAddr`
+    pub(crate) fn is_mac_addr(type_info: &PgTypeInfo) -> bool {
+        matches!(type_info.name(), "MACADDR" | "MACADDR8")
+    }
+
+    pub(crate) fn new(value: PgValue<'a>) -> Result<Self, Error> {
+        let bytes = value.buf()?.0;
+        if !matches!(bytes.len(), 6 | 8) {
+            return Err(Error::custom(format!(
+                "invalid MAC address length {}",
+                bytes.len()
+            )));
+        }
+
+        Ok(PgMacAddr { bytes })
+    }
+
+    fn text(&self) -> String {
+        let octets: Vec<String> = self.bytes.iter().map(|b| format!("{b:02x}")).collect();
+        octets.join(":")
+    }
+}
+
+impl<'de> Deserializer<'de> for PgMacAddr<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.text())
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_bytes(self.bytes)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(SeqDeserializer::new(self.bytes.iter().copied()))
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if len != self.bytes.len() {
+            return Err(Error::invalid_length(self.bytes.len(), &visitor));
+        }
+
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        unit unit_struct map struct enum identifier
+    }
+}
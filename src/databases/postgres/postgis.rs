@@ -0,0 +1,171 @@
+use serde::de::Error as _;
+use serde_json::{json, Map, Value};
+use sqlx::postgres::PgTypeInfo;
+
+use crate::error::Error;
+
+use super::value::is_extension_type;
+
+/// Flags of the EWKB geometry type, the type itself is in the lowest byte
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// Whether values of the type are PostGIS geometries or geographies, which are sent as EWKB
+pub(crate) fn is_postgis(type_info: &PgTypeInfo) -> bool {
+    is_extension_type(type_info, &["geometry", "geography"])
+}
+
+/// Converts a PostGIS geometry in EWKB into a GeoJSON geometry, e.g.
+/// `{"type": "Point", "coordinates": [1.0, 2.0], "srid": 4326}`.
+///
+/// The SRID isn't part of GeoJSON, so it is a foreign member that `geojson::Geometry` keeps
+/// and other types can ignore. Positions have a third and fourth value for Z and M.
+pub(crate) fn geojson(bytes: &[u8]) -> Result<Value, Error> {
+    let mut ewkb = Ewkb {
+        bytes,
+        little: true,
+    };
+    let geometry = ewkb.geometry()?;
+
+    if !ewkb.bytes.is_empty() {
+        return Err(Error::custom("trailing bytes after EWKB geometry"));
+    }
+
+    Ok(geometry)
+}
+
+/// A reader of (E)WKB, which sets its byte order at the start of every geometry
+struct Ewkb<'a> {
+    bytes: &'a [u8],
+    little: bool,
+}
+
+impl Ewkb<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        if self.bytes.len() < N {
+            return Err(Error::custom("unexpected end of EWKB geometry"));
+        }
+
+        let (head, tail) = self.bytes.split_at(N);
+        self.bytes = tail;
+        Ok(head.try_into().expect("took exactly N bytes"))
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.take()?;
+        Ok(match self.little {
+            true => u32::from_le_bytes(bytes),
+            false => u32::from_be_bytes(bytes),
+        })
+    }
+
+    fn f64(&mut self) -> Result<f64, Error> {
+        let bytes = self.take()?;
+        Ok(match self.little {
+            true => f64::from_le_bytes(bytes),
+            false => f64::from_be_bytes(bytes),
+        })
+    }
+
+    /// The number of elements that follow, each at least `min_size` bytes long, so that a bad
+    /// length can't allocate unbounded memory
+    fn len(&mut self, min_size: usize) -> Result<usize, Error> {
+        let len = self.u32()?;
+        let len = usize::try_from(len).map_err(Error::custom)?;
+
+        if len.saturating_mul(min_size) > self.bytes.len() {
+            return Err(Error::custom(format!("invalid EWKB length {len}")));
+        }
+        Ok(len)
+    }
+
+    /// `[x, y]`, with z and m when present. An empty point has NaN coordinates, which GeoJSON
+    /// writes as an empty position
+    fn position(&mut self, dims: usize) -> Result<Value, Error> {
+        let values = (0..dims)
+            .map(|_| self.f64())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if values.iter().all(|value| value.is_nan()) {
+            return Ok(json!([]));
+        }
+        Ok(json!(values))
+    }
+
+    fn positions(&mut self, dims: usize) -> Result<Value, Error> {
+        let len = self.len(8 * dims)?;
+        let positions = (0..len)
+            .map(|_| self.position(dims))
+            .collect::<Result<_, _>>()?;
+        Ok(Value::Array(positions))
+    }
+
+    /// The rings of a polygon, an empty one is just its count of positions
+    fn rings(&mut self, dims: usize) -> Result<Value, Error> {
+        let len = self.len(4)?;
+        let rings = (0..len)
+            .map(|_| self.positions(dims))
+            .collect::<Result<_, _>>()?;
+        Ok(Value::Array(rings))
+    }
+
+    /// The geometries of a multi geometry or collection, each of them a whole (E)WKB geometry
+    fn members(&mut self) -> Result<Vec<Value>, Error> {
+        // An empty member is at least its byte order and type
+        let len = self.len(5)?;
+        (0..len).map(|_| self.geometry()).collect()
+    }
+
+    /// The coordinates of the members of a multi geometry, which are all of the same type
+    fn multi(&mut self) -> Result<Value, Error> {
+        let members = self.members()?;
+        let coordinates = members
+            .into_iter()
+            .map(|mut member| member["coordinates"].take());
+        Ok(Value::Array(coordinates.collect()))
+    }
+
+    fn geometry(&mut self) -> Result<Value, Error> {
+        self.little = match self.take::<1>()? {
+            [0] => false,
+            [1] => true,
+            [other] => return Err(Error::custom(format!("invalid EWKB byte order {other}"))),
+        };
+
+        let kind = self.u32()?;
+        let srid = if kind & EWKB_SRID != 0 {
+            Some(self.u32()?)
+        } else {
+            None
+        };
+        let dims = 2 + usize::from(kind & EWKB_Z != 0) + usize::from(kind & EWKB_M != 0);
+
+        let mut geometry = Map::new();
+        let (name, member, value) = match kind & 0xff {
+            1 => ("Point", "coordinates", self.position(dims)?),
+            2 => ("LineString", "coordinates", self.positions(dims)?),
+            3 => ("Polygon", "coordinates", self.rings(dims)?),
+            4 => ("MultiPoint", "coordinates", self.multi()?),
+            5 => ("MultiLineString", "coordinates", self.multi()?),
+            6 => ("MultiPolygon", "coordinates", self.multi()?),
+            7 => {
+                let geometries = self.members()?;
+                ("GeometryCollection", "geometries", Value::Array(geometries))
+            }
+            other => {
+                return Err(Error::custom(format!(
+                    "unsupported EWKB geometry type {other}"
+                )))
+            }
+        };
+
+        geometry.insert("type".to_owned(), json!(name));
+        geometry.insert(member.to_owned(), value);
+        if let Some(srid) = srid {
+            geometry.insert("srid".to_owned(), json!(srid));
+        }
+
+        Ok(Value::Object(geometry))
+    }
+}
@@ -0,0 +1,436 @@
+use serde::{
+    de::{
+        DeserializeSeed, EnumAccess, Error as _, IntoDeserializer, MapAccess, SeqAccess,
+        VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use sqlx::{
+    postgres::{types::PgRange, PgTypeInfo, PgTypeKind},
+    Type, TypeInfo as _,
+};
+
+use crate::error::Error;
+
+use super::value::{kind, PgBuf, PgValue};
+
+const EMPTY: u8 = 0x01;
+const LB_INC: u8 = 0x02;
+const UB_INC: u8 = 0x04;
+const LB_INF: u8 = 0x08;
+const UB_INF: u8 = 0x10;
+
+const FIELDS: [&str; 5] = ["start", "end", "start_inclusive", "end_inclusive", "empty"];
+
+/// A Postgres range, e.g. `INT4RANGE` or `TSTZRANGE`.
+///
+/// Ranges deserialize as maps of `start`, `end`, `start_inclusive`, `end_inclusive` and
+/// `empty`, with unbounded ends as NULL (see `serde_sqlx::types::PgRange`), or as a pair of
+/// `std::ops::Bound`s. Like sqlx's own `PgRange`, an empty range is a pair of unbounded ends.
+pub(crate) struct PgRangeValue<'a> {
+    start: PgValue<'a>,
+    end: PgValue<'a>,
+    flags: u8,
+}
+
+impl<'a> PgRangeValue<'a> {
+    /// Whether values of the type are ranges, and should be read with `PgRangeValue`
+    pub(crate) fn is_range(type_info: &PgTypeInfo) -> bool {
+        matches!(kind(type_info), Some(PgTypeKind::Range(_)))
+    }
+
+    pub(crate) fn new(value: PgValue<'a>) -> Result<Self, Error> {
+        let Some(PgTypeKind::Range(element_type)) = kind(&value.type_info) else {
+            return Err(Error::custom(format!(
+                "expected a range, found {}",
+                value.type_info
+            )));
+        };
+
+        // https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/rangetypes.c (range_send)
+        let mut buf = value.buf()?;
+        let flags = buf.take(1)?[0];
+
+        let mut bound = |present: bool| -> Result<PgValue<'a>, Error> {
+            let bytes = if present {
+                let len = buf.i32()?;
+                let len = usize::try_from(len)
+                    .map_err(|_| Error::custom(format!("invalid range bound length {len}")))?;
+                Some(buf.take(len)?)
+            } else {
+                None
+            };
+
+            Ok(PgValue {
+                bytes,
+                type_info: element_type.clone(),
+            })
+        };
+
+        let is_empty = flags & EMPTY != 0;
+        let start = bound(!is_empty && flags & LB_INF == 0)?;
+        let end = bound(!is_empty && flags & UB_INF == 0)?;
+
+        Ok(PgRangeValue { start, end, flags })
+    }
+
+    fn bounds(self) -> PgRangeBounds<'a> {
+        PgRangeBounds {
+            bounds: [
+                PgRangeBound {
+                    value: self.start,
+                    inclusive: self.flags & LB_INC != 0,
+                },
+                PgRangeBound {
+                    value: self.end,
+                    inclusive: self.flags & UB_INC != 0,
+                },
+            ]
+            .into_iter(),
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for PgRangeValue<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(PgRangeFields {
+            range: self,
+            index: 0,
+        })
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(self.bounds())
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if len != 2 {
+            return Err(Error::invalid_length(2, &visitor));
+        }
+
+        visitor.visit_seq(self.bounds())
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct map struct enum identifier
+    }
+}
+
+struct PgRangeFields<'a> {
+    range: PgRangeValue<'a>,
+    index: usize,
+}
+
+impl<'de> MapAccess<'de> for PgRangeFields<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let Some(field) = FIELDS.get(self.index) else {
+            return Ok(None);
+        };
+
+        seed.deserialize(field.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<U>(&mut self, seed: U) -> Result<U::Value, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        let index = self.index;
+        self.index += 1;
+
+        let flags = self.range.flags;
+        match index {
+            0 => seed.deserialize(self.range.start.clone()),
+            1 => seed.deserialize(self.range.end.clone()),
+            2 => seed.deserialize((flags & LB_INC != 0).into_deserializer()),
+            3 => seed.deserialize((flags & UB_INC != 0).into_deserializer()),
+            _ => seed.deserialize((flags & EMPTY != 0).into_deserializer()),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(FIELDS.len() - self.index)
+    }
+}
+
+struct PgRangeBounds<'a> {
+    bounds: std::array::IntoIter<PgRangeBound<'a>, 2>,
+}
+
+impl<'de> SeqAccess<'de> for PgRangeBounds<'de> {
+    type Error = Error;
+
+    fn next_element_seed<U>(&mut self, seed: U) -> Result<Option<U::Value>, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        self.bounds
+            .next()
+            .map(|bound| seed.deserialize(bound))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.bounds.len())
+    }
+}
+
+/// One end of a range, deserializes as `std::ops::Bound` or as an `Option` of the value
+struct PgRangeBound<'a> {
+    /// NULL if the range is unbounded on this end
+    value: PgValue<'a>,
+    inclusive: bool,
+}
+
+impl<'de> Deserializer<'de> for PgRangeBound<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value.deserialize_option(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> EnumAccess<'de> for PgRangeBound<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<U>(self, seed: U) -> Result<(U::Value, Self::Variant), Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        let variant = match (&self.value.bytes, self.inclusive) {
+            (None, _) => "Unbounded",
+            (Some(_), true) => "Included",
+            (Some(_), false) => "Excluded",
+        };
+
+        seed.deserialize(variant.into_deserializer())
+            .map(|variant| (variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for PgRangeBound<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<U>(self, seed: U) -> Result<U::Value, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.value)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value.deserialize_map(visitor)
+    }
+}
+
+/// A Postgres multirange, e.g. `INT4MULTIRANGE`, which deserializes as a sequence of ranges
+pub(crate) struct PgMultirange<'a> {
+    buf: PgBuf<'a>,
+    range_type: PgTypeInfo,
+    remaining: usize,
+}
+
+impl<'a> PgMultirange<'a> {
+    /// sqlx doesn't know about multiranges, so they can only be recognized by their name. Only
+    /// the builtin ones are supported as the binary format doesn't say what the ranges hold.
+    fn range_type(type_info: &PgTypeInfo) -> Option<PgTypeInfo> {
+        let range_type = match type_info.name().to_ascii_lowercase().as_str() {
+            "int4multirange" => PgRange::<i32>::type_info(),
+            "int8multirange" => PgRange::<i64>::type_info(),
+            "nummultirange" => PgRange::<rust_decimal::Decimal>::type_info(),
+            "tsmultirange" => PgRange::<chrono::NaiveDateTime>::type_info(),
+            "tstzmultirange" => PgRange::<chrono::DateTime<chrono::Utc>>::type_info(),
+            "datemultirange" => PgRange::<chrono::NaiveDate>::type_info(),
+            _ => return None,
+        };
+
+        Some(range_type)
+    }
+
+    /// Whether values of the type are multiranges, and should be read with `PgMultirange`
+    pub(crate) fn is_multirange(type_info: &PgTypeInfo) -> bool {
+        Self::range_type(type_info).is_some()
+    }
+
+    pub(crate) fn new(value: PgValue<'a>) -> Result<Self, Error> {
+        let range_type = Self::range_type(&value.type_info).ok_or_else(|| {
+            Error::custom(format!("unsupported multirange type {}", value.type_info))
+        })?;
+
+        // https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/multirangetypes.c (multirange_send)
+        let mut buf = value.buf()?;
+        let len = buf.i32()?;
+        let remaining = usize::try_from(len)
+            .map_err(|_| Error::custom(format!("invalid multirange length {len}")))?;
+
+        Ok(PgMultirange {
+            buf,
+            range_type,
+            remaining,
+        })
+    }
+}
+
+impl<'de> Deserializer<'de> for PgMultirange<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(self)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> SeqAccess<'de> for PgMultirange<'de> {
+    type Error = Error;
+
+    fn next_element_seed<U>(&mut self, seed: U) -> Result<Option<U::Value>, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+
+        // Every range is prefixed by its length
+        let len = self.buf.i32()?;
+        let len = usize::try_from(len)
+            .map_err(|_| Error::custom(format!("invalid range length {len}")))?;
+        let value = PgValue {
+            bytes: Some(self.buf.take(len)?),
+            type_info: self.range_type.clone(),
+        };
+
+        seed.deserialize(PgRangeValue::new(value)?).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
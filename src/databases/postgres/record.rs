@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use serde::{
+    de::{DeserializeSeed, Error as _, IntoDeserializer, MapAccess, SeqAccess, Visitor},
+    forward_to_deserialize_any, Deserializer,
+};
+use sqlx::postgres::{types::Oid, PgTypeInfo, PgTypeKind};
+
+use crate::error::Error;
+
+use super::value::{builtin_type_info, kind, PgBuf, PgValue};
+
+/// A Postgres composite value, either of a named type (`CREATE TYPE ... AS (...)`) or an
+/// anonymous `ROW(...)`.
+///
+/// Named composites deserialize as maps from attribute name to value, so they work with
+/// structs. Anonymous records have no attribute names, so they deserialize positionally into
+/// tuples or structs (in field order), and as maps their keys are `f1`, `f2` etc. just like
+/// Postgres' own `row_to_json`.
+pub(crate) struct PgRecord<'a> {
+    buf: PgBuf<'a>,
+    /// Attribute names and types, only known for named composite types
+    attributes: Option<Arc<[(String, PgTypeInfo)]>>,
+    len: usize,
+}
+
+impl<'a> PgRecord<'a> {
+    /// Whether values of the type are records, and should be read with `PgRecord`
+    pub(crate) fn is_record(type_info: &PgTypeInfo) -> bool {
+        match kind(type_info) {
+            Some(PgTypeKind::Composite(_)) => true,
+            _ => sqlx::TypeInfo::name(type_info) == "RECORD",
+        }
+    }
+
+    pub(crate) fn new(value: PgValue<'a>) -> Result<Self, Error> {
+        let attributes = match kind(&value.type_info) {
+            Some(PgTypeKind::Composite(attributes)) => Some(attributes.clone()),
+            _ => None,
+        };
+
+        // https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/rowtypes.c (record_send)
+        let mut buf = value.buf()?;
+        let len = buf.i32()?;
+        let len = usize::try_from(len)
+            .map_err(|_| Error::custom(format!("invalid record length {len}")))?;
+
+        Ok(PgRecord {
+            buf,
+            attributes,
+            len,
+        })
+    }
+
+    fn fields(self) -> PgRecordFields<'a> {
+        PgRecordFields {
+            buf: self.buf,
+            attributes: self.attributes,
+            index: 0,
+            len: self.len,
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for PgRecord<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.attributes.is_some() {
+            visitor.visit_map(self.fields())
+        } else {
+            visitor.visit_seq(self.fields())
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(self.fields())
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.len != len {
+            return Err(Error::invalid_length(self.len, &visitor));
+        }
+
+        visitor.visit_seq(self.fields())
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self.fields())
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct struct enum identifier
+    }
+}
+
+struct PgRecordFields<'a> {
+    buf: PgBuf<'a>,
+    attributes: Option<Arc<[(String, PgTypeInfo)]>>,
+    index: usize,
+    len: usize,
+}
+
+impl<'a> PgRecordFields<'a> {
+    fn next_value(&mut self) -> Result<PgValue<'a>, Error> {
+        // Every field is prefixed by its type and length, a length of -1 means NULL
+        let oid = Oid(self.buf.u32()?);
+        let len = self.buf.i32()?;
+        let bytes = match usize::try_from(len) {
+            Ok(len) => Some(self.buf.take(len)?),
+            Err(_) => None,
+        };
+
+        // The attribute type is preferred as it is also resolved for user defined types
+        let type_info = match &self.attributes {
+            Some(attributes) => match attributes.get(self.index) {
+                Some((_, type_info)) => type_info.clone(),
+                None => builtin_type_info(oid)?,
+            },
+            None => builtin_type_info(oid)?,
+        };
+
+        self.index += 1;
+
+        Ok(PgValue { bytes, type_info })
+    }
+}
+
+impl<'de> SeqAccess<'de> for PgRecordFields<'de> {
+    type Error = Error;
+
+    fn next_element_seed<U>(&mut self, seed: U) -> Result<Option<U::Value>, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+
+        seed.deserialize(self.next_value()?).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+impl<'de> MapAccess<'de> for PgRecordFields<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+
+        let name = match &self.attributes {
+            Some(attributes) => attributes.get(self.index).map(|(name, _)| name.clone()),
+            None => None,
+        };
+        let name = name.unwrap_or_else(|| format!("f{}", self.index + 1));
+
+        seed.deserialize(name.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<U>(&mut self, seed: U) -> Result<U::Value, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.next_value()?)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
@@ -0,0 +1,537 @@
+use std::borrow::Cow;
+
+use serde::{
+    de::{
+        value::BorrowedStrDeserializer, DeserializeSeed, Error as _, IntoDeserializer, MapAccess,
+        SeqAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use sqlx::{
+    postgres::{PgTypeInfo, PgTypeKind},
+    TypeInfo as _,
+};
+
+use crate::{deserializers::visit_integer_variant, error::Error};
+
+use super::json::PgJson;
+
+/// A Postgres array sent as text, which only the simple query protocol (e.g. `sqlx::raw_sql`)
+/// does.
+///
+/// The array literal is parsed into its elements, which deserialize like text columns of the
+/// element type: numbers, booleans, timestamps, JSON and strings are read the same way as in
+/// the binary format, elements of other types are given as the text Postgres shows for them.
+/// Like `PgArray`, multi-dimensional arrays are sequences of sub-arrays, and maps are keyed by
+/// the index of every element.
+pub(crate) struct PgTextArray<'a> {
+    elements: Vec<PgTextElement<'a>>,
+    element_type: PgTypeInfo,
+    /// Index of the first element, Postgres arrays start at 1 unless created otherwise
+    lower_bound: i32,
+}
+
+enum PgTextElement<'a> {
+    Null,
+    /// Quoted elements with escapes in them are the only ones that aren't borrowed
+    Text(Cow<'a, str>),
+    Array(PgTextArray<'a>),
+}
+
+impl<'a> PgTextArray<'a> {
+    pub(crate) fn new(text: &'a str, type_info: &PgTypeInfo) -> Result<Self, Error> {
+        let element_type = match type_info.kind() {
+            PgTypeKind::Array(element_type) => element_type.clone(),
+            _ => {
+                return Err(Error::custom(format!(
+                    "{} is not an array",
+                    type_info.name()
+                )))
+            }
+        };
+
+        // https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/arrayfuncs.c (array_out)
+        let mut parser = PgTextArrayParser {
+            text,
+            pos: 0,
+            // Only BOX uses another delimiter, as its own text contains commas
+            delimiter: if element_type.name() == "BOX" {
+                b';'
+            } else {
+                b','
+            },
+            lower_bounds: Vec::new(),
+        };
+
+        parser.dimensions()?;
+        let array = parser.array(0, &element_type)?;
+        if parser.pos != text.len() {
+            return Err(parser.invalid());
+        }
+
+        Ok(array)
+    }
+
+    fn elements(self) -> PgTextArrayElements<'a> {
+        PgTextArrayElements {
+            elements: self.elements.into_iter(),
+            element_type: self.element_type,
+            index: self.lower_bound,
+        }
+    }
+}
+
+struct PgTextArrayParser<'a> {
+    text: &'a str,
+    pos: usize,
+    delimiter: u8,
+    /// Only given in the text when an array doesn't start at 1, e.g. `[0:1]={1,2}`
+    lower_bounds: Vec<i32>,
+}
+
+impl<'a> PgTextArrayParser<'a> {
+    fn invalid(&self) -> Error {
+        Error::custom(format!("invalid array literal: {}", self.text))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), Error> {
+        if self.peek() != Some(byte) {
+            return Err(self.invalid());
+        }
+
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Reads the `[lower:upper]` bounds of every dimension and the `=` after them, if any
+    fn dimensions(&mut self) -> Result<(), Error> {
+        if self.peek() != Some(b'[') {
+            return Ok(());
+        }
+
+        let end = self.text.find('=').ok_or_else(|| self.invalid())?;
+        for dim in self.text[..end].split_terminator(']') {
+            let lower_bound = dim
+                .strip_prefix('[')
+                .and_then(|dim| dim.split_once(':'))
+                .and_then(|(lower, _)| lower.parse().ok())
+                .ok_or_else(|| self.invalid())?;
+            self.lower_bounds.push(lower_bound);
+        }
+
+        self.pos = end + 1;
+        Ok(())
+    }
+
+    fn array(&mut self, depth: usize, element_type: &PgTypeInfo) -> Result<PgTextArray<'a>, Error> {
+        self.expect(b'{')?;
+
+        let mut elements = Vec::new();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+        } else {
+            loop {
+                elements.push(self.element(depth, element_type)?);
+
+                match self.peek() {
+                    Some(b'}') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    Some(byte) if byte == self.delimiter => self.pos += 1,
+                    _ => return Err(self.invalid()),
+                }
+            }
+        }
+
+        Ok(PgTextArray {
+            elements,
+            element_type: element_type.clone(),
+            lower_bound: self.lower_bounds.get(depth).copied().unwrap_or(1),
+        })
+    }
+
+    fn element(
+        &mut self,
+        depth: usize,
+        element_type: &PgTypeInfo,
+    ) -> Result<PgTextElement<'a>, Error> {
+        match self.peek() {
+            Some(b'{') => Ok(PgTextElement::Array(self.array(depth + 1, element_type)?)),
+            Some(b'"') => self.quoted().map(PgTextElement::Text),
+            _ => {
+                let start = self.pos;
+                loop {
+                    match self.peek() {
+                        Some(byte) if byte == self.delimiter || byte == b'}' => break,
+                        Some(_) => self.pos += 1,
+                        None => return Err(self.invalid()),
+                    }
+                }
+
+                let text = &self.text[start..self.pos];
+                if text.eq_ignore_ascii_case("NULL") {
+                    Ok(PgTextElement::Null)
+                } else {
+                    Ok(PgTextElement::Text(Cow::Borrowed(text)))
+                }
+            }
+        }
+    }
+
+    /// A double quoted element, where a backslash escapes the character after it
+    fn quoted(&mut self) -> Result<Cow<'a, str>, Error> {
+        self.expect(b'"')?;
+
+        let rest = &self.text[self.pos..];
+        let end = rest.find(['"', '\\']).ok_or_else(|| self.invalid())?;
+        if rest.as_bytes()[end] == b'"' {
+            self.pos += end + 1;
+            return Ok(Cow::Borrowed(&rest[..end]));
+        }
+
+        let mut unescaped = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += index + 1;
+                    return Ok(Cow::Owned(unescaped));
+                }
+                '\\' => unescaped.push(chars.next().ok_or_else(|| self.invalid())?.1),
+                c => unescaped.push(c),
+            }
+        }
+
+        Err(self.invalid())
+    }
+}
+
+impl<'de> Deserializer<'de> for PgTextArray<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(self.elements())
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.elements.len() != len {
+            return Err(Error::invalid_length(self.elements.len(), &visitor));
+        }
+
+        visitor.visit_seq(self.elements())
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self.elements())
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq struct enum identifier
+    }
+}
+
+struct PgTextArrayElements<'a> {
+    elements: std::vec::IntoIter<PgTextElement<'a>>,
+    element_type: PgTypeInfo,
+    /// The Postgres index of the next element
+    index: i32,
+}
+
+impl<'a> PgTextArrayElements<'a> {
+    fn next_value(&mut self) -> Option<PgTextValue<'a>> {
+        self.elements.next().map(|element| PgTextValue {
+            element,
+            type_info: self.element_type.clone(),
+        })
+    }
+}
+
+impl<'de> SeqAccess<'de> for PgTextArrayElements<'de> {
+    type Error = Error;
+
+    fn next_element_seed<U>(&mut self, seed: U) -> Result<Option<U::Value>, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        self.next_value()
+            .map(|value| seed.deserialize(value))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+impl<'de> MapAccess<'de> for PgTextArrayElements<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.elements.len() == 0 {
+            return Ok(None);
+        }
+
+        seed.deserialize(self.index.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<U>(&mut self, seed: U) -> Result<U::Value, Self::Error>
+    where
+        U: DeserializeSeed<'de>,
+    {
+        self.index += 1;
+
+        let value = self
+            .next_value()
+            .ok_or_else(|| Error::custom("no more array elements"))?;
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+/// An element of a text array, or a sub-array of a multi-dimensional one
+struct PgTextValue<'a> {
+    element: PgTextElement<'a>,
+    type_info: PgTypeInfo,
+}
+
+impl<'de> PgTextValue<'de> {
+    fn visit_text<V>(
+        text: Cow<'de, str>,
+        type_info: &PgTypeInfo,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        fn parse<T: std::str::FromStr>(text: &str) -> Result<T, Error>
+        where
+            T::Err: std::error::Error + Send + Sync + 'static,
+        {
+            text.parse()
+                .map_err(|err: T::Err| Error::decode(err.into()))
+        }
+
+        match type_info.name() {
+            "INT8" => visitor.visit_i64(parse(&text)?),
+            "INT4" => visitor.visit_i32(parse(&text)?),
+            "INT2" => visitor.visit_i16(parse(&text)?),
+            "OID" => visitor.visit_u32(parse(&text)?),
+            // Rust parses `NaN`, `Infinity` and `-Infinity` just like Postgres prints them
+            "FLOAT4" => visitor.visit_f32(parse(&text)?),
+            "FLOAT8" => visitor.visit_f64(parse(&text)?),
+            "BOOL" => visitor.visit_bool(text == "t"),
+            // In the formats the binary values are given in
+            "TIMESTAMP" => {
+                let ts = chrono::NaiveDateTime::parse_from_str(&text, "%Y-%m-%d %H:%M:%S%.f")
+                    .map_err(|err| Error::decode(err.into()))?;
+                visitor.visit_string(ts.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+            }
+            "TIMESTAMPTZ" => {
+                let ts = chrono::DateTime::parse_from_str(&text, "%Y-%m-%d %H:%M:%S%.f%#z")
+                    .map_err(|err| Error::decode(err.into()))?;
+                visitor.visit_string(ts.to_utc().fixed_offset().to_rfc3339())
+            }
+            "JSON" | "JSONB" => PgJson::from_bytes(text.as_bytes(), false)?
+                .into_deserializer()
+                .deserialize_any(visitor),
+            _ => match text {
+                Cow::Borrowed(text) => visitor.visit_borrowed_str(text),
+                Cow::Owned(text) => visitor.visit_string(text),
+            },
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for PgTextValue<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.element {
+            PgTextElement::Null => visitor.visit_none(),
+            PgTextElement::Text(text) => Self::visit_text(text, &self.type_info, visitor),
+            PgTextElement::Array(array) => array.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.element {
+            PgTextElement::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// NUMERIC is exact unless a float is expected, as in the binary format
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.element {
+            PgTextElement::Text(text) if self.type_info.name() == "NUMERIC" => {
+                let num: f64 = text
+                    .parse()
+                    .map_err(|_| Error::custom("Failed to parse NUMERIC as f64"))?;
+                visitor.visit_f64(num)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.element {
+            PgTextElement::Array(array) => array.deserialize_tuple(len, visitor),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.element {
+            PgTextElement::Array(array) => array.deserialize_map(visitor),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    /// Enum elements are unit variants named by the text, or by an integer code
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let text = match self.element {
+            PgTextElement::Text(text) => text,
+            element => {
+                let value = PgTextValue {
+                    element,
+                    type_info: self.type_info,
+                };
+                return value.deserialize_any(visitor);
+            }
+        };
+
+        match (self.type_info.name(), text) {
+            ("INT2" | "INT4" | "INT8", text) => {
+                let code = text
+                    .parse()
+                    .map_err(|err: std::num::ParseIntError| Error::decode(err.into()))?;
+                visit_integer_variant(code, variants, visitor)
+            }
+            (_, Cow::Borrowed(text)) => visitor.visit_enum(BorrowedStrDeserializer::new(text)),
+            (_, Cow::Owned(text)) => visitor.visit_enum(text.into_deserializer()),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 char str string
+        bytes byte_buf unit unit_struct seq struct identifier
+    }
+}
@@ -0,0 +1,703 @@
+use serde::{
+    de::{value::BorrowedStrDeserializer, Error as _, IntoDeserializer, Visitor},
+    Deserializer,
+};
+use sqlx::{
+    postgres::{
+        types::{Oid, PgInterval, PgMoney, PgRange, PgTimeTz},
+        PgHasArrayType, PgTypeInfo, PgTypeKind, PgValueRef,
+    },
+    Postgres, Type, TypeInfo as _, ValueRef as _,
+};
+
+use crate::deserializers::visit_integer_variant;
+use crate::error::{Error, ErrorContext};
+
+use super::{
+    array::PgArray,
+    geometry::PgGeometry,
+    hstore::PgHstore,
+    interval::PgIntervalValue,
+    json::PgJson,
+    network::{PgInet, PgMacAddr},
+    range::{PgMultirange, PgRangeValue},
+    record::PgRecord,
+    vector::PgVector,
+};
+
+/// A Postgres value in the binary wire format.
+///
+/// sqlx can only decode whole columns, but array elements (and later record fields) are values
+/// of their own. Decoding everything from the raw bytes means a value deserializes the same
+/// way no matter where it appears.
+#[derive(Debug, Clone)]
+pub(crate) struct PgValue<'a> {
+    /// `None` if the value is NULL
+    pub(crate) bytes: Option<&'a [u8]>,
+    pub(crate) type_info: PgTypeInfo,
+}
+
+impl<'a> PgValue<'a> {
+    pub(crate) fn new(value: PgValueRef<'a>) -> Result<Self, Error> {
+        let bytes = if value.is_null() {
+            None
+        } else {
+            Some(value.as_bytes().map_err(Error::custom)?)
+        };
+
+        Ok(PgValue {
+            bytes,
+            type_info: value.type_info().into_owned(),
+        })
+    }
+
+    /// The bytes of the value, NULL is an error
+    pub(crate) fn buf(&self) -> Result<PgBuf<'a>, Error> {
+        self.bytes.map(PgBuf).ok_or(Error::UnexpectedNull {
+            context: ErrorContext::default(),
+        })
+    }
+}
+
+/// Every `Deserializer` method with its extra arguments, used to forward them all
+macro_rules! deserializer_methods {
+    ($macro:ident) => {
+        $macro! {
+            deserialize_any()
+            deserialize_bool()
+            deserialize_i8()
+            deserialize_i16()
+            deserialize_i32()
+            deserialize_i64()
+            deserialize_u8()
+            deserialize_u16()
+            deserialize_u32()
+            deserialize_u64()
+            deserialize_f32()
+            deserialize_f64()
+            deserialize_char()
+            deserialize_str()
+            deserialize_string()
+            deserialize_bytes()
+            deserialize_byte_buf()
+            deserialize_option()
+            deserialize_unit()
+            deserialize_unit_struct(name: &'static str)
+            deserialize_newtype_struct(name: &'static str)
+            deserialize_seq()
+            deserialize_tuple(len: usize)
+            deserialize_tuple_struct(name: &'static str, len: usize)
+            deserialize_map()
+            deserialize_struct(name: &'static str, fields: &'static [&'static str])
+            deserialize_enum(name: &'static str, variants: &'static [&'static str])
+            deserialize_identifier()
+            deserialize_ignored_any()
+        }
+    };
+}
+
+/// A value made up of other values, each kind has its own `Deserializer`
+pub(crate) enum PgNested<'a> {
+    Array(PgArray<'a>),
+    Record(PgRecord<'a>),
+    Range(PgRangeValue<'a>),
+    Multirange(PgMultirange<'a>),
+    Interval(PgIntervalValue),
+    Hstore(PgHstore<'a>),
+    Inet(PgInet),
+    MacAddr(PgMacAddr<'a>),
+    Geometry(PgGeometry),
+    Vector(PgVector),
+}
+
+impl<'a> PgNested<'a> {
+    /// Whether values of the type are made up of other values
+    pub(crate) fn is_nested(type_info: &PgTypeInfo) -> bool {
+        PgArray::is_array(type_info)
+            || PgRecord::is_record(type_info)
+            || PgRangeValue::is_range(type_info)
+            || PgMultirange::is_multirange(type_info)
+            || PgIntervalValue::is_interval(type_info)
+            || PgHstore::is_hstore(type_info)
+            || PgInet::is_inet(type_info)
+            || PgMacAddr::is_mac_addr(type_info)
+            || PgGeometry::is_geometry(type_info)
+            || PgVector::is_vector(type_info)
+    }
+
+    /// `None` if the value is NULL or a scalar
+    fn new(value: &PgValue<'a>) -> Result<Option<Self>, Error> {
+        if value.bytes.is_none() {
+            return Ok(None);
+        }
+
+        let type_info = &value.type_info;
+        let nested = if PgArray::is_array(type_info) {
+            PgNested::Array(PgArray::new(value.clone())?)
+        } else if PgRecord::is_record(type_info) {
+            PgNested::Record(PgRecord::new(value.clone())?)
+        } else if PgRangeValue::is_range(type_info) {
+            PgNested::Range(PgRangeValue::new(value.clone())?)
+        } else if PgMultirange::is_multirange(type_info) {
+            PgNested::Multirange(PgMultirange::new(value.clone())?)
+        } else if PgIntervalValue::is_interval(type_info) {
+            PgNested::Interval(PgIntervalValue::new(value.clone())?)
+        } else if PgHstore::is_hstore(type_info) {
+            PgNested::Hstore(PgHstore::new(value.clone())?)
+        } else if PgInet::is_inet(type_info) {
+            PgNested::Inet(PgInet::new(value.clone())?)
+        } else if PgMacAddr::is_mac_addr(type_info) {
+            PgNested::MacAddr(PgMacAddr::new(value.clone())?)
+        } else if PgGeometry::is_geometry(type_info) {
+            PgNested::Geometry(PgGeometry::new(value.clone())?)
+        } else if PgVector::is_vector(type_info) {
+            PgNested::Vector(PgVector::new(value.clone())?)
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(nested))
+    }
+}
+
+macro_rules! forward_to_nested {
+    ($($method:ident($($arg:ident: $ty:ty),*))*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self {
+                    PgNested::Array(array) => array.$method($($arg,)* visitor),
+                    PgNested::Record(record) => record.$method($($arg,)* visitor),
+                    PgNested::Range(range) => range.$method($($arg,)* visitor),
+                    PgNested::Multirange(multirange) => multirange.$method($($arg,)* visitor),
+                    PgNested::Interval(interval) => interval.$method($($arg,)* visitor),
+                    PgNested::Hstore(hstore) => hstore.$method($($arg,)* visitor),
+                    PgNested::Inet(inet) => inet.$method($($arg,)* visitor),
+                    PgNested::MacAddr(mac_addr) => mac_addr.$method($($arg,)* visitor),
+                    PgNested::Geometry(geometry) => geometry.$method($($arg,)* visitor),
+                    PgNested::Vector(vector) => vector.$method($($arg,)* visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for PgNested<'de> {
+    type Error = Error;
+
+    deserializer_methods!(forward_to_nested);
+}
+
+/// Nested values handle every method themselves, scalars are visited by their type alone
+macro_rules! nested_or_scalar {
+    ($fallback:ident: $($method:ident($($arg:ident: $ty:ty),*))*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                match PgNested::new(&self)? {
+                    Some(nested) => nested.$method($($arg,)* visitor),
+                    None => {
+                        $(let _ = $arg;)*
+                        self.$fallback(visitor)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for PgValue<'de> {
+    type Error = Error;
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.bytes.is_none() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    nested_or_scalar! {
+        deserialize_float:
+        deserialize_f32()
+        deserialize_f64()
+    }
+
+    nested_or_scalar! {
+        deserialize_scalar:
+        deserialize_any()
+        deserialize_bool()
+        deserialize_i8()
+        deserialize_i16()
+        deserialize_i32()
+        deserialize_i64()
+        deserialize_u8()
+        deserialize_u16()
+        deserialize_u32()
+        deserialize_u64()
+        deserialize_char()
+        deserialize_str()
+        deserialize_string()
+        deserialize_bytes()
+        deserialize_byte_buf()
+        deserialize_unit()
+        deserialize_unit_struct(name: &'static str)
+        deserialize_seq()
+        deserialize_tuple(len: usize)
+        deserialize_tuple_struct(name: &'static str, len: usize)
+        deserialize_map()
+        deserialize_struct(name: &'static str, fields: &'static [&'static str])
+        deserialize_identifier()
+        deserialize_ignored_any()
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match PgNested::new(&self)? {
+            Some(nested) => nested.deserialize_enum(name, variants, visitor),
+            None => self.deserialize_variant(variants, visitor),
+        }
+    }
+}
+
+impl<'de> PgValue<'de> {
+    /// Visits a NUMERIC as a float for float targets, at the cost of precision
+    fn deserialize_float<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let Some(bytes) = self.bytes.filter(|_| self.type_info.name() == "NUMERIC") else {
+            return self.deserialize_scalar(visitor);
+        };
+
+        let num: f64 = match numeric(&mut PgBuf(bytes))? {
+            PgNumeric::Number(s) => s
+                .parse()
+                .map_err(|_| Error::custom("Failed to parse NUMERIC as f64"))?,
+            PgNumeric::NaN => f64::NAN,
+            PgNumeric::Infinity => f64::INFINITY,
+            PgNumeric::NegInfinity => f64::NEG_INFINITY,
+        };
+
+        visitor.visit_f64(num)
+    }
+
+    /// Visits a value that isn't made up of other values, based on its type alone
+    fn deserialize_scalar<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.visit_scalar(visitor, false)
+    }
+
+    /// Visits a text value, e.g. of an enum type like `CREATE TYPE mood AS ENUM (...)` or TEXT,
+    /// as the unit variant of that name, and an integer as the variant of that code
+    fn deserialize_variant<V>(
+        self,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let Some(bytes) = self.bytes else {
+            return visitor.visit_none();
+        };
+        let mut buf = PgBuf(bytes);
+
+        let code = match self.type_info.name() {
+            "INT2" => buf.i16()?.into(),
+            "INT4" => buf.i32()?.into(),
+            "INT8" => buf.i64()?,
+            _ => return self.visit_scalar(visitor, true),
+        };
+
+        visit_integer_variant(code, variants, visitor)
+    }
+
+    fn visit_scalar<V>(self, visitor: V, as_enum: bool) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let Some(bytes) = self.bytes else {
+            return visitor.visit_none();
+        };
+        let mut buf = PgBuf(bytes);
+
+        match self.type_info.name() {
+            "FLOAT4" => visitor.visit_f32(buf.f32()?),
+            "FLOAT8" => visitor.visit_f64(buf.f64()?),
+            // Exact, for `rust_decimal::Decimal`, `bigdecimal::BigDecimal` or `String`
+            "NUMERIC" => match numeric(&mut buf)? {
+                PgNumeric::Number(s) => visitor.visit_string(s),
+                PgNumeric::NaN => visitor.visit_borrowed_str("NaN"),
+                PgNumeric::Infinity => visitor.visit_borrowed_str("Infinity"),
+                PgNumeric::NegInfinity => visitor.visit_borrowed_str("-Infinity"),
+            },
+            "INT8" => visitor.visit_i64(buf.i64()?),
+            "INT4" => visitor.visit_i32(buf.i32()?),
+            "INT2" => visitor.visit_i16(buf.i16()?),
+            // In the currency's smallest unit, e.g. cents, as the number of decimal places
+            // depends on the server's `lc_monetary`
+            "MONEY" => visitor.visit_i64(buf.i64()?),
+            "BOOL" => visitor.visit_bool(buf.take(1)?[0] != 0),
+            "DATE" => visitor.visit_string(date(&mut buf)?.to_string()),
+            "TIME" => visitor.visit_string(time(&mut buf)?),
+            "TIMETZ" => {
+                let time = time(&mut buf)?;
+                // Postgres counts the offset in seconds west of UTC
+                let offset = buf.i32()?;
+                let offset = chrono::FixedOffset::west_opt(offset)
+                    .ok_or_else(|| Error::custom(format!("invalid TIMETZ offset: {offset}")))?;
+                visitor.visit_string(format!("{time}{offset}"))
+            }
+            // No offset, in the format `chrono::NaiveDateTime` deserializes from
+            "TIMESTAMP" => {
+                let ts = timestamp(&mut buf)?;
+                visitor.visit_string(ts.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+            }
+            "TIMESTAMPTZ" => {
+                let ts = timestamp(&mut buf)?.and_utc().fixed_offset();
+                visitor.visit_string(ts.to_rfc3339())
+            }
+            "UUID" => {
+                let uuid =
+                    uuid::Uuid::from_slice(bytes).map_err(|err| Error::decode(err.into()))?;
+                visitor.visit_string(uuid.to_string())
+            }
+            "BYTEA" => visitor.visit_borrowed_bytes(bytes),
+            "JSON" | "JSONB" => {
+                let is_jsonb = self.type_info.name() == "JSONB";
+                let value = PgJson::from_bytes(bytes, is_jsonb)?;
+
+                value.into_deserializer().deserialize_any(visitor)
+            }
+            // A version number followed by the path as text
+            "JSONPATH" => {
+                let _version = buf.take(1)?;
+                let s = std::str::from_utf8(buf.0).map_err(|err| Error::decode(err.into()))?;
+                visitor.visit_borrowed_str(s)
+            }
+            #[cfg(feature = "postgis")]
+            "geometry" | "geography" => super::postgis::geojson(bytes)?
+                .into_deserializer()
+                .deserialize_any(visitor)
+                .map_err(Error::json),
+            // TEXT, VARCHAR, CHAR, NAME, enums and anything else sent as UTF-8 text
+            _other => {
+                let s = std::str::from_utf8(bytes).map_err(|err| Error::decode(err.into()))?;
+                if as_enum {
+                    visitor.visit_enum(BorrowedStrDeserializer::new(s))
+                } else {
+                    visitor.visit_borrowed_str(s)
+                }
+            }
+        }
+    }
+}
+
+/// The kind of a type, `None` for types that sqlx hasn't resolved, see `builtin_type_info`
+pub(crate) fn kind(type_info: &PgTypeInfo) -> Option<&PgTypeKind> {
+    // Unresolved types panic when asked for their kind, they are only declared by name
+    type_info.oid().map(|_| type_info.kind())
+}
+
+/// Whether the type is one of the given types of an extension, e.g. `hstore`. Extension types
+/// have no fixed oid, so they are known by name
+pub(crate) fn is_extension_type(type_info: &PgTypeInfo, names: &[&str]) -> bool {
+    names.contains(&type_info.name())
+}
+
+/// Type info for a builtin type, which is all that an anonymous `ROW(...)` or the header of an
+/// array tells about its contents. Other types can't be read safely without knowing their
+/// binary format, so they are an error
+pub(crate) fn builtin_type_info(oid: Oid) -> Result<PgTypeInfo, Error> {
+    macro_rules! builtin {
+        ($($ty:ty => $oid:literal, $array_oid:literal;)*) => {
+            Ok(match oid.0 {
+                $(
+                    $oid => <$ty as Type<Postgres>>::type_info(),
+                    $array_oid => <$ty as PgHasArrayType>::array_type_info(),
+                )*
+                // Types without a Rust counterpart in sqlx (or only behind a feature) are
+                // declared by name
+                19 => PgTypeInfo::with_name("NAME"),
+                114 => PgTypeInfo::with_name("JSON"),
+                142 => PgTypeInfo::with_name("XML"),
+                143 => PgTypeInfo::with_name("XML[]"),
+                199 => PgTypeInfo::with_name("JSON[]"),
+                600 => PgTypeInfo::with_name("POINT"),
+                601 => PgTypeInfo::with_name("LSEG"),
+                602 => PgTypeInfo::with_name("PATH"),
+                603 => PgTypeInfo::with_name("BOX"),
+                604 => PgTypeInfo::with_name("POLYGON"),
+                628 => PgTypeInfo::with_name("LINE"),
+                629 => PgTypeInfo::with_name("LINE[]"),
+                650 => PgTypeInfo::with_name("CIDR"),
+                651 => PgTypeInfo::with_name("CIDR[]"),
+                718 => PgTypeInfo::with_name("CIRCLE"),
+                705 => PgTypeInfo::with_name("UNKNOWN"),
+                719 => PgTypeInfo::with_name("CIRCLE[]"),
+                774 => PgTypeInfo::with_name("MACADDR8"),
+                775 => PgTypeInfo::with_name("MACADDR8[]"),
+                829 => PgTypeInfo::with_name("MACADDR"),
+                869 => PgTypeInfo::with_name("INET"),
+                1003 => PgTypeInfo::with_name("NAME[]"),
+                1014 => PgTypeInfo::with_name("CHAR[]"),
+                1015 => PgTypeInfo::with_name("VARCHAR[]"),
+                1017 => PgTypeInfo::with_name("POINT[]"),
+                1018 => PgTypeInfo::with_name("LSEG[]"),
+                1019 => PgTypeInfo::with_name("PATH[]"),
+                1020 => PgTypeInfo::with_name("BOX[]"),
+                1027 => PgTypeInfo::with_name("POLYGON[]"),
+                1040 => PgTypeInfo::with_name("MACADDR[]"),
+                1041 => PgTypeInfo::with_name("INET[]"),
+                1042 => PgTypeInfo::with_name("CHAR"),
+                1043 => PgTypeInfo::with_name("VARCHAR"),
+                2249 => PgTypeInfo::with_name("RECORD"),
+                2287 => PgTypeInfo::with_name("RECORD[]"),
+                4072 => PgTypeInfo::with_name("JSONPATH"),
+                4073 => PgTypeInfo::with_name("JSONPATH[]"),
+                4451 => PgTypeInfo::with_name("int4multirange"),
+                4532 => PgTypeInfo::with_name("nummultirange"),
+                4533 => PgTypeInfo::with_name("tsmultirange"),
+                4534 => PgTypeInfo::with_name("tstzmultirange"),
+                4535 => PgTypeInfo::with_name("datemultirange"),
+                4536 => PgTypeInfo::with_name("int8multirange"),
+                other => {
+                    return Err(Error::decode(
+                        format!("unsupported type with oid {other}").into(),
+                    ))
+                }
+            })
+        };
+    }
+
+    builtin! {
+        bool => 16, 1000;
+        &[u8] => 17, 1001;
+        i8 => 18, 1002;
+        i64 => 20, 1016;
+        i16 => 21, 1005;
+        i32 => 23, 1007;
+        String => 25, 1009;
+        Oid => 26, 1028;
+        f32 => 700, 1021;
+        f64 => 701, 1022;
+        chrono::NaiveDate => 1082, 1182;
+        chrono::NaiveTime => 1083, 1183;
+        chrono::NaiveDateTime => 1114, 1115;
+        chrono::DateTime<chrono::Utc> => 1184, 1185;
+        PgMoney => 790, 791;
+        PgInterval => 1186, 1187;
+        PgTimeTz<chrono::NaiveTime, chrono::FixedOffset> => 1266, 1270;
+        rust_decimal::Decimal => 1700, 1231;
+        uuid::Uuid => 2950, 2951;
+        serde_json::Value => 3802, 3807;
+        PgRange<i32> => 3904, 3905;
+        PgRange<rust_decimal::Decimal> => 3906, 3907;
+        PgRange<chrono::NaiveDateTime> => 3908, 3909;
+        PgRange<chrono::DateTime<chrono::Utc>> => 3910, 3911;
+        PgRange<chrono::NaiveDate> => 3912, 3913;
+        PgRange<i64> => 3926, 3927;
+    }
+}
+
+/// Cursor over the bytes of a binary value, reads fail instead of panicking on short input
+pub(crate) struct PgBuf<'a>(pub(crate) &'a [u8]);
+
+impl<'a> PgBuf<'a> {
+    pub(crate) fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < len {
+            return Err(Error::custom(format!(
+                "unexpected end of binary value, needed {len} bytes but only {} are left",
+                self.0.len()
+            )));
+        }
+
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.take(N)?.try_into().expect("took exactly N bytes"))
+    }
+
+    pub(crate) fn i16(&mut self) -> Result<i16, Error> {
+        self.array().map(i16::from_be_bytes)
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, Error> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    pub(crate) fn i32(&mut self) -> Result<i32, Error> {
+        self.array().map(i32::from_be_bytes)
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, Error> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    pub(crate) fn i64(&mut self) -> Result<i64, Error> {
+        self.array().map(i64::from_be_bytes)
+    }
+
+    pub(crate) fn f32(&mut self) -> Result<f32, Error> {
+        self.array().map(f32::from_be_bytes)
+    }
+
+    pub(crate) fn f64(&mut self) -> Result<f64, Error> {
+        self.array().map(f64::from_be_bytes)
+    }
+}
+
+/// Dates and timestamps are sent relative to 2000-01-01
+fn pg_epoch() -> chrono::NaiveDateTime {
+    chrono::NaiveDate::from_ymd_opt(2000, 1, 1)
+        .expect("valid date")
+        .and_hms_opt(0, 0, 0)
+        .expect("valid time")
+}
+
+fn date(buf: &mut PgBuf) -> Result<chrono::NaiveDate, Error> {
+    let days = buf.i32()?;
+
+    pg_epoch()
+        .date()
+        .checked_add_signed(chrono::Duration::days(days.into()))
+        .ok_or_else(|| Error::custom(format!("DATE out of range: {days} days")))
+}
+
+/// The end of the day, which Postgres allows as a TIME but `chrono::NaiveTime` can't hold
+const END_OF_DAY: i64 = 86_400_000_000;
+
+/// A TIME as text, in the format `chrono::NaiveTime` deserializes from. Only `24:00:00` doesn't
+/// fit in one and is left to string targets
+fn time(buf: &mut PgBuf) -> Result<String, Error> {
+    let microseconds = buf.i64()?;
+    if microseconds == END_OF_DAY {
+        return Ok("24:00:00".to_owned());
+    }
+
+    let secs = u32::try_from(microseconds / 1_000_000).ok();
+    let nanos = u32::try_from(microseconds % 1_000_000 * 1000).ok();
+
+    secs.zip(nanos)
+        .and_then(|(secs, nanos)| {
+            chrono::NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+        })
+        .map(|time| time.to_string())
+        .ok_or_else(|| Error::custom(format!("TIME out of range: {microseconds} microseconds")))
+}
+
+fn timestamp(buf: &mut PgBuf) -> Result<chrono::NaiveDateTime, Error> {
+    let microseconds = buf.i64()?;
+
+    pg_epoch()
+        .checked_add_signed(chrono::Duration::microseconds(microseconds))
+        .ok_or_else(|| {
+            Error::custom(format!(
+                "TIMESTAMP out of range: {microseconds} microseconds"
+            ))
+        })
+}
+
+/// A NUMERIC value, the number is kept as a decimal string so no precision is lost
+pub(crate) enum PgNumeric {
+    Number(String),
+    NaN,
+    Infinity,
+    NegInfinity,
+}
+
+/// Decodes a NUMERIC, which is sent as base 10000 digits
+pub(crate) fn numeric(buf: &mut PgBuf) -> Result<PgNumeric, Error> {
+    const POSITIVE: u16 = 0x0000;
+    const NEGATIVE: u16 = 0x4000;
+    const NAN: u16 = 0xC000;
+    const INFINITY: u16 = 0xD000;
+    const NEG_INFINITY: u16 = 0xF000;
+
+    let num_digits = buf.i16()?;
+    let weight = buf.i16()? as i32;
+    let sign = buf.u16()?;
+    let scale = buf.u16()? as usize;
+    let digits = (0..num_digits)
+        .map(|_| buf.i16())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let negative = match sign {
+        POSITIVE => false,
+        NEGATIVE => true,
+        NAN => return Ok(PgNumeric::NaN),
+        INFINITY => return Ok(PgNumeric::Infinity),
+        NEG_INFINITY => return Ok(PgNumeric::NegInfinity),
+        other => return Err(Error::custom(format!("invalid NUMERIC sign: {other:#x}"))),
+    };
+
+    // `weight` is the position of the first digit relative to the decimal point, digits
+    // outside of the sent ones are zero
+    let digit = |i: i32| -> i16 {
+        usize::try_from(i)
+            .ok()
+            .and_then(|i| digits.get(i))
+            .copied()
+            .unwrap_or(0)
+    };
+
+    let mut s = String::new();
+    if negative {
+        s.push('-');
+    }
+
+    if weight < 0 {
+        s.push('0');
+    } else {
+        s.push_str(&digit(0).to_string());
+        for i in 1..=weight {
+            s.push_str(&format!("{:04}", digit(i)));
+        }
+    }
+
+    if scale > 0 {
+        let mut fraction = String::new();
+        let mut i = weight + 1;
+        while fraction.len() < scale {
+            fraction.push_str(&format!("{:04}", digit(i)));
+            i += 1;
+        }
+        fraction.truncate(scale);
+
+        s.push('.');
+        s.push_str(&fraction);
+    }
+
+    Ok(PgNumeric::Number(s))
+}
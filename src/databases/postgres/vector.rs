@@ -0,0 +1,294 @@
+use serde::{
+    de::{
+        value::{MapDeserializer, SeqDeserializer},
+        Error as _, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use sqlx::{postgres::PgTypeInfo, TypeInfo as _};
+
+use crate::error::Error;
+
+use super::value::{is_extension_type, PgBuf, PgValue};
+
+/// The limits of pgvector, `VECTOR_MAX_DIM`, `SPARSEVEC_MAX_DIM` and `SPARSEVEC_MAX_NNZ`
+const VECTOR_MAX_DIM: u32 = 16_000;
+const SPARSEVEC_MAX_DIM: u32 = 1_000_000_000;
+const SPARSEVEC_MAX_NNZ: usize = 16_000;
+
+/// A pgvector `vector`, `halfvec` or `sparsevec`, an embedding of `f32`s.
+///
+/// Dense vectors deserialize as a sequence, e.g. `Vec<f32>` or `[f32; N]`, with `halfvec`
+/// elements widened to `f32`. Sparse vectors deserialize as a map/struct of `indices` (from
+/// zero), `values` and `dim` (see `serde_sqlx::types::SparseVector`), or as the dense sequence
+/// they stand for.
+pub(crate) enum PgVector {
+    Dense(Vec<f32>),
+    Sparse {
+        dim: u32,
+        indices: Vec<u32>,
+        values: Vec<f32>,
+    },
+}
+
+impl PgVector {
+    /// Whether values of the type are pgvector vectors, and should be read with `PgVector`
+    pub(crate) fn is_vector(type_info: &PgTypeInfo) -> bool {
+        is_extension_type(type_info, &["vector", "halfvec", "sparsevec"])
+    }
+
+    pub(crate) fn new(value: PgValue) -> Result<Self, Error> {
+        // https://github.com/pgvector/pgvector/blob/master/src/vector.c (vector_send), and
+        // halfvec.c and sparsevec.c next to it
+        let mut buf = value.buf()?;
+
+        let vector = match value.type_info.name() {
+            "vector" => {
+                let dim = dense_dim(&mut buf)?;
+                let values = (0..dim).map(|_| buf.f32()).collect::<Result<_, _>>()?;
+                PgVector::Dense(values)
+            }
+            "halfvec" => {
+                let dim = dense_dim(&mut buf)?;
+                let values = (0..dim)
+                    .map(|_| buf.u16().map(f16_to_f32))
+                    .collect::<Result<_, _>>()?;
+                PgVector::Dense(values)
+            }
+            "sparsevec" => {
+                let dim = u32::try_from(buf.i32()?)
+                    .ok()
+                    .filter(|dim| *dim <= SPARSEVEC_MAX_DIM)
+                    .ok_or_else(|| Error::custom("invalid sparse vector dimensions"))?;
+                let nnz = usize::try_from(buf.i32()?)
+                    .ok()
+                    .filter(|nnz| *nnz <= SPARSEVEC_MAX_NNZ && *nnz as u64 <= u64::from(dim))
+                    .ok_or_else(|| Error::custom("invalid sparse vector length"))?;
+                let _unused = buf.i32()?;
+
+                let indices = (0..nnz)
+                    .map(|_| {
+                        let index = buf.i32()?;
+                        u32::try_from(index)
+                            .ok()
+                            .filter(|index| *index < dim)
+                            .ok_or_else(|| {
+                                Error::custom(format!("sparse vector index {index} out of bounds"))
+                            })
+                    })
+                    .collect::<Result<_, _>>()?;
+                let values = (0..nnz).map(|_| buf.f32()).collect::<Result<_, _>>()?;
+
+                PgVector::Sparse {
+                    dim,
+                    indices,
+                    values,
+                }
+            }
+            other => return Err(Error::custom(format!("unknown vector type {other}"))),
+        };
+
+        Ok(vector)
+    }
+
+    /// The elements of the vector, with the zeros of a sparse vector filled in. Like a dense
+    /// pgvector, that has at most 16,000 of them
+    fn dense(self) -> Result<Vec<f32>, Error> {
+        match self {
+            PgVector::Dense(values) => Ok(values),
+            PgVector::Sparse { dim, .. } if dim > VECTOR_MAX_DIM => Err(Error::custom(format!(
+                "sparse vector of {dim} dimensions is too large for a dense one"
+            ))),
+            PgVector::Sparse {
+                dim,
+                indices,
+                values,
+            } => {
+                // Indices were checked to be below `dim` when read
+                let mut dense = vec![0.0; dim as usize];
+                for (index, value) in indices.into_iter().zip(values) {
+                    dense[index as usize] = value;
+                }
+                Ok(dense)
+            }
+        }
+    }
+}
+
+/// Dense vectors start with their number of elements and an unused `u16`
+fn dense_dim(buf: &mut PgBuf) -> Result<u16, Error> {
+    let dim = buf.u16()?;
+    let _unused = buf.u16()?;
+    Ok(dim)
+}
+
+/// Widens an IEEE 754 half precision float, as `halfvec` sends them
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = u32::from(bits & 0x8000) << 16;
+    let exponent = u32::from((bits >> 10) & 0x1f);
+    let fraction = u32::from(bits & 0x3ff);
+
+    match exponent {
+        // Zero and subnormals, which are normal numbers as an f32
+        0 => {
+            let magnitude = fraction as f32 * 2f32.powi(-24);
+            if sign == 0 {
+                magnitude
+            } else {
+                -magnitude
+            }
+        }
+        // Infinity and NaN
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (fraction << 13)),
+        _ => f32::from_bits(sign | ((exponent + 127 - 15) << 23) | (fraction << 13)),
+    }
+}
+
+impl<'de> Deserializer<'de> for PgVector {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            PgVector::Dense(_) => self.deserialize_seq(visitor),
+            PgVector::Sparse { .. } => self.deserialize_map(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(SeqDeserializer::new(self.dense()?.into_iter()))
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let dense = self.dense()?;
+        if len != dense.len() {
+            return Err(Error::invalid_length(dense.len(), &visitor));
+        }
+
+        visitor.visit_seq(SeqDeserializer::new(dense.into_iter()))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let PgVector::Sparse {
+            dim,
+            indices,
+            values,
+        } = self
+        else {
+            return self.deserialize_seq(visitor);
+        };
+
+        let fields = [
+            ("indices", VectorPart::Indices(indices)),
+            ("values", VectorPart::Values(values)),
+            ("dim", VectorPart::Dim(dim)),
+        ];
+
+        visitor.visit_map(MapDeserializer::new(fields.into_iter()))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct enum identifier
+    }
+}
+
+/// The `indices`, `values` or `dim` of a sparse vector, as a map value
+enum VectorPart {
+    Indices(Vec<u32>),
+    Values(Vec<f32>),
+    Dim(u32),
+}
+
+impl<'de> IntoDeserializer<'de, Error> for VectorPart {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for VectorPart {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            VectorPart::Indices(indices) => {
+                visitor.visit_seq(SeqDeserializer::new(indices.into_iter()))
+            }
+            VectorPart::Values(values) => {
+                visitor.visit_seq(SeqDeserializer::new(values.into_iter()))
+            }
+            VectorPart::Dim(dim) => visitor.visit_u32(dim),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
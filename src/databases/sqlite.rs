@@ -0,0 +1,85 @@
+use crate::{
+    decode_raw,
+    deserializers::{visit_integer_variant, ValueDeserializer, ValueType},
+    error::Error,
+};
+use serde::de::IntoDeserializer as _;
+use sqlx::{Column as _, TypeInfo as _, ValueRef as _};
+
+use super::Database;
+
+impl Database for sqlx::Sqlite {
+    /// SQLite has no JSON type, JSON is stored as plain TEXT and can't be told apart from any
+    /// other string.
+    fn deserialize_json<'a>(
+        _val_ref: <Self as sqlx::Database>::ValueRef<'a>,
+    ) -> Result<Option<serde_json::Value>, Error> {
+        Ok(None)
+    }
+
+    fn deserialize_value<'de, V: serde::de::Visitor<'de>>(
+        deserializer: ValueDeserializer<'de, Self>,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let val_ref = deserializer.value;
+
+        // The value only knows its storage class (INTEGER, REAL, TEXT, BLOB or NULL), the
+        // column carries the declared type, e.g. BOOLEAN or DATETIME
+        let type_info = val_ref.type_info();
+        let storage_class = type_info.name();
+        let declared_type = deserializer.column.type_info().name();
+
+        // Handle enums, by name or by an integer code
+        if let ValueType::Enum(variants) = deserializer.value_type {
+            if storage_class == "INTEGER" {
+                let code = decode_raw::<i64, Self>(val_ref)?;
+                return visit_integer_variant(code, variants, visitor);
+            }
+
+            let v = decode_raw::<String, Self>(val_ref)?;
+            return visitor.visit_enum(v.into_deserializer());
+        }
+
+        // Booleans are stored as 0 or 1
+        if declared_type == "BOOLEAN" || deserializer.value_type == ValueType::Bool {
+            let v = decode_raw::<bool, Self>(val_ref)?;
+            return visitor.visit_bool(v);
+        }
+
+        match declared_type {
+            "DATE" => {
+                let v = decode_raw::<chrono::NaiveDate, Self>(val_ref)?;
+                return visitor.visit_string(v.to_string());
+            }
+            "TIME" => {
+                let v = decode_raw::<chrono::NaiveTime, Self>(val_ref)?;
+                return visitor.visit_string(v.to_string());
+            }
+            "DATETIME" => {
+                let v = decode_raw::<chrono::NaiveDateTime, Self>(val_ref)?;
+                return visitor.visit_string(v.format("%Y-%m-%dT%H:%M:%S%.f").to_string());
+            }
+            _ => {}
+        }
+
+        match storage_class {
+            "INTEGER" => {
+                let v = decode_raw::<i64, Self>(val_ref)?;
+                visitor.visit_i64(v)
+            }
+            "REAL" => {
+                let v = decode_raw::<f64, Self>(val_ref)?;
+                visitor.visit_f64(v)
+            }
+            "BLOB" => {
+                let v = decode_raw::<&[u8], Self>(val_ref)?;
+                visitor.visit_borrowed_bytes(v)
+            }
+            "NULL" => visitor.visit_none(),
+            _other => {
+                let as_str = decode_raw::<&str, Self>(val_ref)?;
+                visitor.visit_borrowed_str(as_str)
+            }
+        }
+    }
+}
@@ -1,34 +1,102 @@
 use crate::databases::Database;
+use crate::error::Error;
 use crate::map_access::RowMapAccess;
-use serde::de::{value::Error as DeError, Deserializer, Visitor};
+use crate::options::DeserializeOptions;
+use crate::seq_access::RowSeqAccess;
+use serde::de::{Deserializer, Expected, Visitor};
 use serde::de::{Error as _, IntoDeserializer};
 use serde::forward_to_deserialize_any;
-use sqlx::{ColumnIndex, Row, TypeInfo, ValueRef};
+use sqlx::{Column as _, ColumnIndex, Row, ValueRef};
+
+/// The options of `from_row` and other functions that don't take any
+static DEFAULT_OPTIONS: DeserializeOptions = DeserializeOptions::new();
 
 pub struct RowDeserializer<'a, DB: Database> {
     pub(crate) row: &'a <DB as sqlx::Database>::Row,
     pub(crate) index: usize,
+    pub(crate) options: &'a DeserializeOptions,
 }
 
 impl<'a, DB: Database> RowDeserializer<'a, DB> {
     pub fn new(row: &'a <DB as sqlx::Database>::Row) -> Self {
-        RowDeserializer { row, index: 0 }
+        Self::with_options(row, &DEFAULT_OPTIONS)
+    }
+
+    pub fn with_options(
+        row: &'a <DB as sqlx::Database>::Row,
+        options: &'a DeserializeOptions,
+    ) -> Self {
+        RowDeserializer {
+            row,
+            index: 0,
+            options,
+        }
     }
 }
 
-impl<'de, 'a, DB: Database> Deserializer<'de> for RowDeserializer<'a, DB>
+impl<'a, DB: Database> RowDeserializer<'a, DB>
 where
     usize: ColumnIndex<<DB as sqlx::Database>::Row>,
 {
-    type Error = DeError;
+    /// Creates a `ValueDeserializer` for the column currently pointed at by `index`
+    pub(crate) fn value_deserializer(&self) -> Result<ValueDeserializer<'a, DB>, Error> {
+        let value = self.row.try_get_raw(self.index).map_err(Error::custom)?;
+        let column = &self.row.columns()[self.index];
+
+        Ok(ValueDeserializer::new(value, column))
+    }
+
+    /// The row as a map from column names to values, starting at the column pointed at by
+    /// `index`
+    fn map_access(&self, fields: Option<&'static [&'static str]>) -> RowMapAccess<'a, DB> {
+        let columns = self.row.columns()[self.index..]
+            .iter()
+            .map(|column| (column.ordinal(), self.options.column_name(column.name())));
+
+        RowMapAccess::new(self.row, self.options, columns, fields)
+    }
+}
 
+impl<'de, DB: Database> Deserializer<'de> for RowDeserializer<'de, DB>
+where
+    usize: ColumnIndex<<DB as sqlx::Database>::Row>,
+{
+    type Error = Error;
+
+    /// The row is `None` when all of its columns are NULL, e.g. after a LEFT JOIN that matched
+    /// nothing, or when the key column is if the options name one.
+    ///
+    /// The fields of `T` aren't known until it is deserialized, which is too late to be `None`,
+    /// so columns that `T` doesn't use are checked too
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        let raw_value = self.row.try_get_raw(0).map_err(DeError::custom)?;
+        let columns = &self.row.columns()[self.index..];
+
+        let is_none = match &self.options.option_key_column {
+            Some(key) => {
+                let column = columns
+                    .iter()
+                    .find(|column| column.name() == key)
+                    .ok_or_else(|| Error::missing_column(key))?;
+                let raw_value = self.row.try_get_raw(column.ordinal());
+                raw_value.map_err(Error::custom)?.is_null()
+            }
+            None => {
+                let mut all_null = true;
+                for column in columns {
+                    let raw_value = self.row.try_get_raw(column.ordinal());
+                    if !raw_value.map_err(Error::custom)?.is_null() {
+                        all_null = false;
+                        break;
+                    }
+                }
+                all_null
+            }
+        };
 
-        if raw_value.is_null() {
+        if is_none {
             visitor.visit_none()
         } else {
             visitor.visit_some(self)
@@ -53,27 +121,44 @@ where
         match self.row.columns().len() {
             0 => return visitor.visit_unit(),
             1 => {}
-            _n => {
-                return self.deserialize_seq(visitor);
-            }
+            // Columns are named, so a row of several is a map unless asked otherwise. This is
+            // what serde's buffered paths see, e.g. untagged enums and internally tagged enums
+            // that look for their tag column first
+            _n if self.options.positional_rows => return self.deserialize_seq(visitor),
+            _n => return self.deserialize_map(visitor),
         };
 
-        let raw_value = self.row.try_get_raw(self.index).map_err(DeError::custom)?;
-        let type_info = raw_value.type_info();
-        let type_name = type_info.name();
-
-        if raw_value.is_null() {
-            return visitor.visit_none();
-        }
-
+        let raw_value = self.row.try_get_raw(self.index).map_err(Error::custom)?;
         // If this is a BOOL[], TEXT[], etc
-        if DB::is_sequence(type_name) {
+        if !raw_value.is_null() && DB::is_sequence(&raw_value.type_info()) {
             return self.deserialize_seq(visitor);
         }
 
+        // An internally tagged enum looks for its tag column even when that is the only one, as
+        // for a unit variant, unless the column holds the whole enum, e.g. as JSON
+        if is_internally_tagged(&visitor) {
+            let is_whole_enum = !raw_value.is_null()
+                && (DB::is_compound(&raw_value.type_info()) || DB::is_map(&raw_value.type_info()));
+            if !is_whole_enum {
+                let column = &self.row.columns()[self.index];
+                let json = if raw_value.is_null() {
+                    None
+                } else {
+                    DB::deserialize_json(raw_value).map_err(|err| err.in_column(column))?
+                };
+
+                return match json {
+                    Some(json) => json
+                        .into_deserializer()
+                        .deserialize_any(visitor)
+                        .map_err(|err| Error::json(err).in_column(column)),
+                    None => self.deserialize_map(visitor),
+                };
+            }
+        }
+
         // Direct all "basic" types down to `ValueDeserializer`
-        let deserializer = ValueDeserializer::<'_, DB>::new(raw_value);
-        deserializer.deserialize_any(visitor)
+        self.value_deserializer()?.deserialize_any(visitor)
     }
 
     /// We treat the row as a map (each column is a key/value pair)
@@ -81,12 +166,15 @@ where
     where
         V: Visitor<'de>,
     {
-        let num_cols = self.row.columns().len();
+        // Unless the only column is a map itself, e.g. a Postgres hstore
+        if self.row.columns().len() == 1 {
+            let raw_value = self.row.try_get_raw(self.index).map_err(Error::custom)?;
+            if !raw_value.is_null() && DB::is_map(&raw_value.type_info()) {
+                return self.value_deserializer()?.deserialize_map(visitor);
+            }
+        }
 
-        visitor.visit_map(RowMapAccess {
-            deserializer: self,
-            num_cols,
-        })
+        visitor.visit_map(self.map_access(None))
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -96,25 +184,67 @@ where
         DB::deserialize_seq(visitor, self)
     }
 
-    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        // A tuple with an element per column is the row itself, even when the only column is
+        // something the database treats as a sequence, e.g. `(Vec<i32>,)` from a Postgres array
+        let num_cols = self.row.columns().len();
+        if num_cols > 1 && len < num_cols {
+            let unused = self.row.columns()[len..]
+                .iter()
+                .map(|column| column.name().to_owned())
+                .collect();
+            self.options.unused_columns.check(unused)?;
+        }
+
+        if len == num_cols {
+            return visitor.visit_seq(RowSeqAccess {
+                deserializer: self,
+                num_cols,
+            });
+        }
+
+        // A lone column that is a tuple by itself, e.g. a Postgres range as a pair of bounds
+        if num_cols == 1 {
+            let raw_value = self.row.try_get_raw(self.index).map_err(Error::custom)?;
+            if !raw_value.is_null() && DB::is_compound(&raw_value.type_info()) {
+                return self.value_deserializer()?.deserialize_tuple(len, visitor);
+            }
+        }
+
         self.deserialize_seq(visitor)
     }
 
-    fn deserialize_struct<V>(
+    fn deserialize_tuple_struct<V>(
         self,
         _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
         fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        let raw_value = self.row.try_get_raw(self.index).map_err(DeError::custom)?;
+        let raw_value = self.row.try_get_raw(self.index).map_err(Error::custom)?;
+        let column = &self.row.columns()[self.index];
+        let is_compound = !raw_value.is_null() && DB::is_compound(&raw_value.type_info());
 
-        if let Some(json) = DB::deserialize_json(raw_value)? {
+        let json = DB::deserialize_json(raw_value).map_err(|err| err.in_column(column))?;
+
+        if let Some(json) = json {
             if let serde_json::Value::Object(ref obj) = json {
                 if fields.len() == 1 {
                     // If there's only one expected field, check if the object already contains it.
@@ -123,7 +253,7 @@ where
                         return json
                             .into_deserializer()
                             .deserialize_any(visitor)
-                            .map_err(DeError::custom);
+                            .map_err(|err| Error::json(err).in_column(column));
                     } else {
                         // Otherwise, wrap the object in a new map keyed by that field name.
                         let mut map = serde_json::Map::new();
@@ -131,7 +261,7 @@ where
                         return map
                             .into_deserializer()
                             .deserialize_any(visitor)
-                            .map_err(DeError::custom);
+                            .map_err(|err| Error::json(err).in_column(column));
                     }
                 } else {
                     // For multiple expected fields, ensure the JSON object already contains all of them.
@@ -139,13 +269,14 @@ where
                         return json
                             .into_deserializer()
                             .deserialize_any(visitor)
-                            .map_err(DeError::custom);
+                            .map_err(|err| Error::json(err).in_column(column));
                     } else {
-                        return Err(DeError::custom(format!(
+                        return Err(Error::custom(format!(
                             "JSON object missing expected keys: expected {:?}, found keys {:?}",
                             fields,
                             obj.keys().collect::<Vec<_>>()
-                        )));
+                        ))
+                        .in_column(column));
                     }
                 }
             } else {
@@ -153,32 +284,34 @@ where
                 return json
                     .into_deserializer()
                     .deserialize_any(visitor)
-                    .map_err(DeError::custom);
+                    .map_err(|err| Error::json(err).in_column(column));
             }
         };
 
-        // Fallback for non-JSON types.
-        self.deserialize_map(visitor)
+        // A lone column that is a struct by itself, e.g. a Postgres range, unless the struct
+        // expects a field with the column's name
+        if is_compound && self.row.columns().len() == 1 && !fields.contains(&column.name()) {
+            return self
+                .value_deserializer()?
+                .deserialize_struct(name, fields, visitor);
+        }
+
+        // Fallback for non-JSON types, the fields are known so column names can be matched to them
+        visitor.visit_map(self.map_access(Some(fields)))
     }
 
     fn deserialize_enum<V>(
         self,
         _name: &'static str,
-        _variants: &'static [&'static str],
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        let raw_value = self.row.try_get_raw(self.index).map_err(DeError::custom)?;
-
-        if raw_value.is_null() {
-            return visitor.visit_none();
-        }
-
         // Direct all "basic" types down to `ValueDeserializer`
-        let mut deserializer = ValueDeserializer::<'_, DB>::new(raw_value);
-        deserializer.value_type = ValueType::Enum;
+        let mut deserializer = self.value_deserializer()?;
+        deserializer.value_type = ValueType::Enum(variants);
         deserializer.deserialize_any(visitor)
     }
 
@@ -186,48 +319,112 @@ where
     where
         V: Visitor<'de>,
     {
-        let raw_value = self.row.try_get_raw(self.index).map_err(DeError::custom)?;
-        if raw_value.is_null() {
-            return visitor.visit_none();
-        }
-
-        let mut deserializer = ValueDeserializer::<'_, DB>::new(raw_value);
+        let mut deserializer = self.value_deserializer()?;
         deserializer.value_type = ValueType::Bool;
         deserializer.deserialize_any(visitor)
     }
 
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value_deserializer()?.deserialize_f32(visitor)
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value_deserializer()?.deserialize_f64(visitor)
+    }
+
     // For other types, forward to deserialize_any.
     forward_to_deserialize_any! {
-        i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        i8 i16 i32 i64 u8 u16 u32 u64 char str string
         bytes byte_buf unit unit_struct
-        tuple_struct identifier ignored_any
+        identifier ignored_any
+    }
+}
+
+/// Whether the visitor is serde's for an internally tagged enum (`#[serde(tag = "...")]`). Those
+/// are deserialized through `deserialize_any`, so what the visitor expects is the only sign of one
+fn is_internally_tagged<'de, V: Visitor<'de>>(visitor: &V) -> bool {
+    (visitor as &dyn Expected)
+        .to_string()
+        .starts_with("internally tagged enum")
+}
+
+/// Visits the unit variant that an integer column stands for, e.g. a status code.
+///
+/// Enums with variants renamed to numbers, like `#[serde(rename = "2")]`, are matched by that
+/// discriminant and other enums by variant index. Codes that match no variant are left to a
+/// `#[serde(other)]` variant if there is one, or become an error.
+pub(crate) fn visit_integer_variant<'de, V>(
+    code: i64,
+    variants: &'static [&'static str],
+    visitor: V,
+) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let by_discriminant = variants
+        .iter()
+        .any(|variant| variant.parse::<i64>().is_ok());
+    if by_discriminant {
+        return visitor.visit_enum(code.to_string().into_deserializer());
+    }
+
+    // Codes that can't be an index at all are unknown variants, just like unknown names
+    match u32::try_from(code) {
+        Ok(index) => visitor.visit_enum(index.into_deserializer()),
+        Err(_) => visitor.visit_enum(code.to_string().into_deserializer()),
     }
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ValueType {
     Any,
-    Enum,
+    /// A unit variant of an enum with the given variants
+    Enum(&'static [&'static str]),
     Bool,
+    /// Only used by databases with values that can be maps themselves, e.g. Postgres arrays
+    Map,
+    /// A sequence, only used by databases with values that are sequences of something other than
+    /// their own type, e.g. the bytes of a Postgres MACADDR
+    Seq,
+    /// A tuple or fixed size array of the given length
+    Tuple(usize),
+    /// A struct with the given name and fields, only used by databases with values that can be
+    /// structs themselves, e.g. Postgres intervals into `std::time::Duration`
+    Struct(&'static str, &'static [&'static str]),
+    /// A float, exact decimals (e.g. NUMERIC) are otherwise given as strings to keep precision
+    Float,
 }
 
 /// An "inner" deserializer
 pub struct ValueDeserializer<'a, DB: Database> {
     pub(crate) value: <DB as sqlx::Database>::ValueRef<'a>,
     pub(crate) value_type: ValueType,
+    /// The column the value was taken from. Some databases (e.g. SQLite) only report the
+    /// declared type of a column here, the value itself only knows its storage class.
+    pub(crate) column: &'a <DB as sqlx::Database>::Column,
 }
 
 impl<'a, DB: Database> ValueDeserializer<'a, DB> {
-    pub fn new(val: <DB as sqlx::Database>::ValueRef<'a>) -> Self {
+    pub fn new(
+        val: <DB as sqlx::Database>::ValueRef<'a>,
+        column: &'a <DB as sqlx::Database>::Column,
+    ) -> Self {
         Self {
             value: val,
             value_type: ValueType::Any,
+            column,
         }
     }
 }
 
-impl<'de, 'a, DB: Database> Deserializer<'de> for ValueDeserializer<'a, DB> {
-    type Error = DeError;
+impl<'de, DB: Database> Deserializer<'de> for ValueDeserializer<'de, DB> {
+    type Error = Error;
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
@@ -240,26 +437,42 @@ impl<'de, 'a, DB: Database> Deserializer<'de> for ValueDeserializer<'a, DB> {
         }
     }
 
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        if self.value.is_null() {
-            return visitor.visit_none();
-        }
-        DB::deserialize_value(self, visitor)
+        let column = self.column;
+
+        let result = if self.value.is_null() {
+            visitor.visit_none()
+        } else {
+            DB::deserialize_value(self, visitor)
+        };
+
+        result.map_err(|err| err.in_column(column))
     }
 
     fn deserialize_enum<V>(
         mut self,
         _name: &'static str,
-        _variants: &'static [&'static str],
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.value_type = ValueType::Enum;
+        self.value_type = ValueType::Enum(variants);
         self.deserialize_any(visitor)
     }
 
@@ -271,10 +484,75 @@ impl<'de, 'a, DB: Database> Deserializer<'de> for ValueDeserializer<'a, DB> {
         self.deserialize_any(visitor)
     }
 
+    fn deserialize_f32<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value_type = ValueType::Float;
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_f64<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value_type = ValueType::Float;
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_map<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value_type = ValueType::Map;
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_seq<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value_type = ValueType::Seq;
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_tuple<V>(mut self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value_type = ValueType::Tuple(len);
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        mut self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.value_type = ValueType::Struct(name, fields);
+        self.deserialize_any(visitor)
+    }
+
     // For other types, forward to deserialize_any.
     forward_to_deserialize_any! {
-        i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
-        bytes byte_buf unit unit_struct newtype_struct struct
-        tuple_struct identifier ignored_any tuple seq map
+        i8 i16 i32 i64 u8 u16 u32 u64 char str string
+        bytes byte_buf unit unit_struct
+        identifier ignored_any
     }
 }
@@ -0,0 +1,270 @@
+use std::fmt::{self, Display};
+
+use serde::de::{Expected, Unexpected};
+use sqlx::{error::BoxDynError, TypeInfo as _};
+
+/// Where in the row an error happened and what was being deserialized at the time.
+///
+/// Every field is optional as not all of them are known at every point, e.g. a missing column
+/// has a name but no index or SQL type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// Name of the column, as reported by the database
+    pub column: Option<String>,
+    /// Index of the column within the row
+    pub index: Option<usize>,
+    /// Name of the SQL type of the column, e.g. `INT4` or `VARCHAR`
+    pub sql_type: Option<String>,
+    /// The Rust type that was requested, e.g. `i32` or `struct User`
+    pub rust_type: Option<String>,
+}
+
+impl ErrorContext {
+    fn with_rust_type(rust_type: impl Display) -> Self {
+        ErrorContext {
+            rust_type: Some(rust_type.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+
+        match (&self.column, self.index) {
+            (Some(column), Some(index)) => {
+                parts.push(format!("column `{column}` at index {index}"))
+            }
+            (Some(column), None) => parts.push(format!("column `{column}`")),
+            (None, Some(index)) => parts.push(format!("column at index {index}")),
+            (None, None) => {}
+        }
+        if let Some(sql_type) = &self.sql_type {
+            parts.push(format!("SQL type {sql_type}"));
+        }
+        if let Some(rust_type) = &self.rust_type {
+            parts.push(format!("Rust type {rust_type}"));
+        }
+
+        if parts.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, " ({})", parts.join(", "))
+    }
+}
+
+/// The error returned when a row can't be deserialized
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The database driver failed to decode a value into an intermediate Rust type
+    Decode {
+        context: ErrorContext,
+        source: BoxDynError,
+    },
+    /// A column was NULL but the Rust type can't represent that (it isn't an `Option`)
+    UnexpectedNull { context: ErrorContext },
+    /// A field of the Rust type has no matching column in the row
+    MissingColumn { context: ErrorContext },
+    /// The value of a column can't be represented by the requested Rust type
+    TypeMismatch {
+        context: ErrorContext,
+        /// A description of what was found instead, e.g. `string "abc"`
+        found: String,
+    },
+    /// A JSON/JSONB value couldn't be parsed or deserialized
+    Json {
+        context: ErrorContext,
+        source: serde_json::Error,
+    },
+    /// Columns of the row that the Rust type didn't use, only raised when
+    /// [`UnusedColumns`](crate::UnusedColumns) asks for it
+    UnusedColumns {
+        context: ErrorContext,
+        /// Names of the unused columns, in the order of the row
+        columns: Vec<String>,
+    },
+    /// Any other error, mostly raised by `Deserialize` implementations
+    Custom {
+        context: ErrorContext,
+        message: String,
+    },
+}
+
+impl Error {
+    /// Where the error happened, see [`ErrorContext`]
+    pub fn context(&self) -> &ErrorContext {
+        match self {
+            Error::Decode { context, .. }
+            | Error::UnexpectedNull { context }
+            | Error::MissingColumn { context }
+            | Error::TypeMismatch { context, .. }
+            | Error::Json { context, .. }
+            | Error::UnusedColumns { context, .. }
+            | Error::Custom { context, .. } => context,
+        }
+    }
+
+    fn context_mut(&mut self) -> &mut ErrorContext {
+        match self {
+            Error::Decode { context, .. }
+            | Error::UnexpectedNull { context }
+            | Error::MissingColumn { context }
+            | Error::TypeMismatch { context, .. }
+            | Error::Json { context, .. }
+            | Error::UnusedColumns { context, .. }
+            | Error::Custom { context, .. } => context,
+        }
+    }
+
+    /// Name of the column the error happened in, if known
+    pub fn column(&self) -> Option<&str> {
+        self.context().column.as_deref()
+    }
+
+    /// Index of the column the error happened in, if known
+    pub fn index(&self) -> Option<usize> {
+        self.context().index
+    }
+
+    /// Name of the SQL type of the column the error happened in, if known
+    pub fn sql_type(&self) -> Option<&str> {
+        self.context().sql_type.as_deref()
+    }
+
+    /// The Rust type that was requested, if known
+    pub fn rust_type(&self) -> Option<&str> {
+        self.context().rust_type.as_deref()
+    }
+
+    /// The Rust type is left unknown, as decoding only ever produces an intermediate type and
+    /// not the one that was requested
+    pub(crate) fn decode(source: BoxDynError) -> Self {
+        Error::Decode {
+            context: ErrorContext::default(),
+            source,
+        }
+    }
+
+    pub(crate) fn missing_column(name: &str) -> Self {
+        Error::MissingColumn {
+            context: ErrorContext {
+                column: Some(name.to_owned()),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub(crate) fn unused_columns(columns: Vec<String>) -> Self {
+        Error::UnusedColumns {
+            context: ErrorContext::default(),
+            columns,
+        }
+    }
+
+    pub(crate) fn json(source: serde_json::Error) -> Self {
+        Error::Json {
+            context: ErrorContext::default(),
+            source,
+        }
+    }
+
+    /// Fills in the column the error happened in, keeping anything already known as the
+    /// innermost information is the most accurate
+    pub(crate) fn in_column(mut self, column: &impl sqlx::Column) -> Self {
+        let context = self.context_mut();
+        let type_info = column.type_info();
+
+        context
+            .column
+            .get_or_insert_with(|| column.name().to_owned());
+        context.index.get_or_insert(column.ordinal());
+        // SQLite reports NULL as the type of columns that aren't taken straight from a table
+        if context.sql_type.is_none() && !type_info.is_null() {
+            context.sql_type = Some(type_info.name().to_owned());
+        }
+
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Decode { source, .. } => write!(f, "failed to decode value: {source}")?,
+            Error::UnexpectedNull { .. } => write!(f, "unexpected NULL value")?,
+            Error::MissingColumn { .. } => write!(f, "missing column")?,
+            Error::TypeMismatch { found, .. } => write!(f, "type mismatch, found {found}")?,
+            Error::Json { source, .. } => write!(f, "invalid JSON: {source}")?,
+            Error::UnusedColumns { columns, .. } => {
+                let columns: Vec<String> = columns.iter().map(|c| format!("`{c}`")).collect();
+                write!(f, "unused columns: {}", columns.join(", "))?
+            }
+            Error::Custom { message, .. } => write!(f, "{message}")?,
+        }
+
+        write!(f, "{}", self.context())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Decode { source, .. } => Some(&**source),
+            Error::Json { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Custom {
+            context: ErrorContext::default(),
+            message: msg.to_string(),
+        }
+    }
+
+    fn invalid_type(unexp: Unexpected, exp: &dyn Expected) -> Self {
+        let context = ErrorContext::with_rust_type(exp);
+
+        match unexp {
+            // This is what serde reports when `visit_none` is called on a non-optional type
+            Unexpected::Option => Error::UnexpectedNull { context },
+            unexp => Error::TypeMismatch {
+                context,
+                found: unexp.to_string(),
+            },
+        }
+    }
+
+    fn invalid_value(unexp: Unexpected, exp: &dyn Expected) -> Self {
+        Error::TypeMismatch {
+            context: ErrorContext::with_rust_type(exp),
+            found: unexp.to_string(),
+        }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Error::missing_column(field)
+    }
+}
+
+/// Allows returning the error from `sqlx::FromRow` implementations and other places that
+/// expect a `sqlx::Error`. The original error is kept as the source so it can be downcast.
+impl From<Error> for sqlx::Error {
+    fn from(err: Error) -> Self {
+        let index = match (err.column(), err.index()) {
+            (Some(column), _) => format!("{column:?}"),
+            (None, Some(index)) => index.to_string(),
+            (None, None) => String::new(),
+        };
+
+        sqlx::Error::ColumnDecode {
+            index,
+            source: Box::new(err),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::de::Deserialize;
+use sqlx::ColumnIndex;
+
+use crate::{databases::Database, deserializers::RowDeserializer};
+
+/// Wrapper that implements `sqlx::FromRow` for any type that implements `Deserialize`.
+///
+/// This allows serde types to be used directly with `sqlx::query_as` and friends without
+/// also deriving `sqlx::FromRow`:
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::PgPool) -> Result<(), sqlx::Error> {
+/// use serde_sqlx::Serde;
+///
+/// #[derive(serde::Deserialize)]
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// let users: Vec<Serde<User>> = sqlx::query_as("SELECT id, name FROM users")
+///     .fetch_all(&pool)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Serde<T>(pub T);
+
+impl<T> Serde<T> {
+    /// Unwraps the deserialized value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Serde<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Serde<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Serde<T> {
+    fn from(value: T) -> Self {
+        Serde(value)
+    }
+}
+
+impl<'r, R, T> sqlx::FromRow<'r, R> for Serde<T>
+where
+    R: sqlx::Row,
+    R::Database: Database + sqlx::Database<Row = R>,
+    usize: ColumnIndex<R>,
+    T: Deserialize<'r>,
+{
+    fn from_row(row: &'r R) -> Result<Self, sqlx::Error> {
+        let deserializer: RowDeserializer<'r, R::Database> = RowDeserializer::new(row);
+
+        T::deserialize(deserializer).map(Serde).map_err(Into::into)
+    }
+}
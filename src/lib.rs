@@ -1,45 +1,87 @@
 use databases::Database;
 use deserializers::RowDeserializer;
-use serde::de::{value::Error as DeError, Deserialize};
-use serde::de::{DeserializeOwned, Error};
+use serde::de::{Deserialize, DeserializeOwned};
 
 use sqlx::postgres::PgRow;
 
 mod databases;
 mod deserializers;
+mod error;
+mod from_row;
 mod map_access;
+mod options;
 mod seq_access;
+pub mod types;
+
+pub use error::{Error, ErrorContext};
+pub use from_row::Serde;
+pub use options::{DeserializeOptions, RenameRule, UnusedColumns};
 
 /// Convenience function to deserialize a generic `sqlx::Row` into a serde Deserializable `T`
-pub fn from_row<DB, T>(row: <DB as sqlx::Database>::Row) -> Result<T, DeError>
+pub fn from_row<DB, T>(row: <DB as sqlx::Database>::Row) -> Result<T, Error>
 where
     DB: Database,
     usize: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
     T: DeserializeOwned,
 {
-    let deserializer: RowDeserializer<'_, DB> = RowDeserializer::new(&row);
+    from_row_ref::<DB, T>(&row)
+}
+
+/// Deserializes a borrowed `sqlx::Row` into `T` without copying strings or bytes.
+///
+/// Fields such as `&'r str`, `&'r [u8]` and `#[serde(borrow)] Cow<'r, str>` borrow directly
+/// from the row, so `T` can't outlive it.
+pub fn from_row_ref<'r, DB, T>(row: &'r <DB as sqlx::Database>::Row) -> Result<T, Error>
+where
+    DB: Database,
+    usize: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
+    T: Deserialize<'r>,
+{
+    let deserializer: RowDeserializer<'r, DB> = RowDeserializer::new(row);
+    T::deserialize(deserializer)
+}
+
+/// Like [`from_row`], with [`DeserializeOptions`] such as how column names are matched to fields
+pub fn from_row_with<DB, T>(
+    row: <DB as sqlx::Database>::Row,
+    options: &DeserializeOptions,
+) -> Result<T, Error>
+where
+    DB: Database,
+    usize: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
+    T: DeserializeOwned,
+{
+    from_row_ref_with::<DB, T>(&row, options)
+}
+
+/// Like [`from_row_ref`], with [`DeserializeOptions`] such as how column names are matched to
+/// fields
+pub fn from_row_ref_with<'r, DB, T>(
+    row: &'r <DB as sqlx::Database>::Row,
+    options: &'r DeserializeOptions,
+) -> Result<T, Error>
+where
+    DB: Database,
+    usize: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
+    T: Deserialize<'r>,
+{
+    let deserializer: RowDeserializer<'r, DB> = RowDeserializer::with_options(row, options);
     T::deserialize(deserializer)
 }
 
 /// Convenience function: deserialize a PgRow into any T that implements Deserialize
 #[deprecated = "Use the more generic `from_row` function instead"]
-pub fn from_pg_row<T>(row: PgRow) -> Result<T, DeError>
+pub fn from_pg_row<T>(row: PgRow) -> Result<T, Error>
 where
     T: for<'de> Deserialize<'de>,
 {
     from_row::<sqlx::Postgres, T>(row)
 }
 
-fn decode_raw<'a, T, DB>(raw_value: <DB as sqlx::Database>::ValueRef<'a>) -> Result<T, DeError>
+fn decode_raw<'a, T, DB>(raw_value: <DB as sqlx::Database>::ValueRef<'a>) -> Result<T, Error>
 where
     DB: sqlx::Database,
     T: sqlx::Decode<'a, DB>,
 {
-    T::decode(raw_value).map_err(|err| {
-        DeError::custom(format!(
-            "Failed to decode {} value: {:?}",
-            std::any::type_name::<T>(),
-            err,
-        ))
-    })
+    T::decode(raw_value).map_err(Error::decode)
 }
@@ -1,48 +1,230 @@
-use serde::de::{self, value::Error as DeError, IntoDeserializer, MapAccess};
-use serde::ser::Error as _;
+use std::borrow::Cow;
 
-use sqlx::{Column, Row};
+use serde::de::{
+    self, value::BorrowedStrDeserializer, Error as _, IntoDeserializer, MapAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+
+use sqlx::{Column, Row, ValueRef};
 
 use crate::databases::Database;
-use crate::deserializers::{RowDeserializer, ValueDeserializer};
+use crate::deserializers::ValueDeserializer;
+use crate::error::Error;
+use crate::options::DeserializeOptions;
+
+/// Separators between the name of a struct field and the columns of the nested struct, e.g.
+/// `author.name` or `author__name`
+const NESTED_SEPARATORS: [&str; 2] = [".", "__"];
+
+/// Splits a column name at its first nesting separator
+fn split_nested(name: &str) -> Option<(&str, &str)> {
+    NESTED_SEPARATORS
+        .iter()
+        .filter_map(|separator| {
+            let at = name.find(separator)?;
+            Some((at, &name[..at], &name[at + separator.len()..]))
+        })
+        .min_by_key(|(at, _, _)| *at)
+        .map(|(_, head, rest)| (head, rest))
+}
+
+/// What a key of the map is given as its value
+enum MapValue<'a> {
+    /// The column at this index
+    Column(usize),
+    /// The columns of a nested struct, with the name left after the struct's prefix
+    Nested(Vec<(usize, &'a str)>),
+}
 
 pub(crate) struct RowMapAccess<'a, DB: Database> {
-    pub(crate) deserializer: RowDeserializer<'a, DB>,
-    pub(crate) num_cols: usize,
+    row: &'a <DB as sqlx::Database>::Row,
+    options: &'a DeserializeOptions,
+    entries: std::vec::IntoIter<(Cow<'a, str>, MapValue<'a>)>,
+    value: Option<MapValue<'a>>,
+    /// Columns that no struct field matched, reported once all entries have been read
+    unused: Vec<usize>,
+}
+
+impl<'a, DB: Database> RowMapAccess<'a, DB> {
+    /// A map of the given columns, which are the index of each column and its name.
+    ///
+    /// When the fields of a struct are known, columns whose name starts with one of them and a
+    /// nesting separator are grouped into a nested struct for that field.
+    pub(crate) fn new(
+        row: &'a <DB as sqlx::Database>::Row,
+        options: &'a DeserializeOptions,
+        columns: impl IntoIterator<Item = (usize, &'a str)>,
+        fields: Option<&'static [&'static str]>,
+    ) -> Self {
+        let mut entries: Vec<(Cow<'a, str>, MapValue<'a>)> = Vec::new();
+        let mut unused = Vec::new();
+
+        for (index, name) in columns {
+            let nested = fields.and_then(|fields| {
+                let (head, rest) = split_nested(name)?;
+                let key = options.field_key(head, Some(fields));
+                fields.contains(&&*key).then_some((key, rest))
+            });
+
+            let Some((key, rest)) = nested else {
+                let key = options.field_key(name, fields);
+                if fields.is_some_and(|fields| !fields.contains(&&*key)) {
+                    unused.push(index);
+                }
+                entries.push((key, MapValue::Column(index)));
+                continue;
+            };
+
+            let group = entries.iter_mut().find_map(|entry| match entry {
+                (entry_key, MapValue::Nested(columns)) if *entry_key == key => Some(columns),
+                _ => None,
+            });
+            match group {
+                Some(columns) => columns.push((index, rest)),
+                None => entries.push((key, MapValue::Nested(vec![(index, rest)]))),
+            }
+        }
+
+        RowMapAccess {
+            row,
+            options,
+            entries: entries.into_iter(),
+            value: None,
+            unused,
+        }
+    }
 }
 
-impl<'de, 'a, DB: Database> MapAccess<'de> for RowMapAccess<'a, DB>
+impl<'de, DB: Database> MapAccess<'de> for RowMapAccess<'de, DB>
 where
     usize: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
 {
-    type Error = DeError;
+    type Error = Error;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
     where
         K: de::DeserializeSeed<'de>,
     {
-        if self.deserializer.index < self.num_cols {
-            let col_name = self.deserializer.row.columns()[self.deserializer.index].name();
-            // Use the column name as the key
-            seed.deserialize(col_name.into_deserializer()).map(Some)
-        } else {
-            Ok(None)
+        let Some((key, value)) = self.entries.next() else {
+            let unused = std::mem::take(&mut self.unused)
+                .into_iter()
+                .map(|index| self.row.columns()[index].name().to_owned())
+                .collect();
+            self.options.unused_columns.check(unused)?;
+            return Ok(None);
+        };
+        self.value = Some(value);
+
+        // Column names live as long as the row so they can be borrowed, unless the options
+        // renamed them
+        match key {
+            Cow::Borrowed(key) => seed.deserialize(BorrowedStrDeserializer::new(key)),
+            Cow::Owned(key) => seed.deserialize(key.into_deserializer()),
         }
+        .map(Some)
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
     where
         V: de::DeserializeSeed<'de>,
     {
-        let value = self
-            .deserializer
-            .row
-            .try_get_raw(self.deserializer.index)
-            .map_err(DeError::custom)?;
-        let type_deserializer = ValueDeserializer::<'_, DB>::new(value);
+        match self.value.take() {
+            Some(MapValue::Column(index)) => {
+                let value = self.row.try_get_raw(index).map_err(Error::custom)?;
+                let column = &self.row.columns()[index];
+
+                seed.deserialize(ValueDeserializer::<DB>::new(value, column))
+            }
+            Some(MapValue::Nested(columns)) => seed.deserialize(NestedRowDeserializer::<DB> {
+                row: self.row,
+                options: self.options,
+                columns,
+            }),
+            None => Err(Error::custom("value requested before its key")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// The columns of a struct nested in the row, e.g. `author.id` and `author.name` for an
+/// `author` field
+pub(crate) struct NestedRowDeserializer<'a, DB: Database> {
+    row: &'a <DB as sqlx::Database>::Row,
+    options: &'a DeserializeOptions,
+    columns: Vec<(usize, &'a str)>,
+}
+
+impl<'a, DB: Database> NestedRowDeserializer<'a, DB> {
+    fn into_map(self, fields: Option<&'static [&'static str]>) -> RowMapAccess<'a, DB> {
+        RowMapAccess::new(self.row, self.options, self.columns, fields)
+    }
+}
+
+impl<'de, DB: Database> de::Deserializer<'de> for NestedRowDeserializer<'de, DB>
+where
+    usize: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
+{
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self.into_map(None))
+    }
+
+    /// The nested struct is NULL when all of its columns are, e.g. after a LEFT JOIN that
+    /// matched nothing
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        for &(index, _) in &self.columns {
+            let value = self.row.try_get_raw(index).map_err(Error::custom)?;
+            if !value.is_null() {
+                return visitor.visit_some(self);
+            }
+        }
+
+        visitor.visit_none()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
 
-        self.deserializer.index += 1;
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self.into_map(Some(fields)))
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
 
-        seed.deserialize(type_deserializer)
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map enum identifier
     }
 }
@@ -0,0 +1,269 @@
+use std::{borrow::Cow, fmt, sync::Arc};
+
+use crate::error::Error;
+
+/// A case convention that column names are converted into, see
+/// [`DeserializeOptions::rename_columns`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RenameRule {
+    /// `userId` and `UserID` become `user_id`
+    SnakeCase,
+    /// `user_id` and `UserId` become `userId`
+    CamelCase,
+}
+
+impl RenameRule {
+    fn apply(self, name: &str) -> String {
+        let snake = to_snake_case(name);
+
+        match self {
+            RenameRule::SnakeCase => snake,
+            RenameRule::CamelCase => {
+                let mut camel = String::with_capacity(snake.len());
+                for (i, word) in snake.split('_').filter(|word| !word.is_empty()).enumerate() {
+                    let mut chars = word.chars();
+                    if i > 0 {
+                        camel.extend(chars.next().map(|first| first.to_ascii_uppercase()));
+                    }
+                    camel.push_str(chars.as_str());
+                }
+                camel
+            }
+        }
+    }
+}
+
+/// Splits words at `_`, `-`, spaces and case changes, e.g. `HTTPStatus` into `http_status`
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if matches!(c, '_' | '-' | ' ') {
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            let starts_word = prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower);
+            if starts_word && !snake.ends_with('_') {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+
+    if snake.ends_with('_') {
+        snake.pop();
+    }
+    snake
+}
+
+type MapColumnName = dyn Fn(&str) -> String + Send + Sync;
+
+/// What to do with columns that the Rust type doesn't use, see
+/// [`DeserializeOptions::unused_columns`]
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub enum UnusedColumns {
+    /// Unused columns are skipped silently
+    #[default]
+    Ignore,
+    /// Unused columns are an [`Error::UnusedColumns`]
+    Deny,
+    /// The [`Error::UnusedColumns`] is given to the callback and deserialization carries on
+    Warn(Arc<dyn Fn(&Error) + Send + Sync>),
+}
+
+impl UnusedColumns {
+    /// Reports unused columns to `callback`, e.g. to log them
+    pub fn warn<F>(callback: F) -> Self
+    where
+        F: Fn(&Error) + Send + Sync + 'static,
+    {
+        UnusedColumns::Warn(Arc::new(callback))
+    }
+
+    /// Errors, warns or does nothing about the unused `columns`, if there are any
+    pub(crate) fn check(&self, columns: Vec<String>) -> Result<(), Error> {
+        if columns.is_empty() {
+            return Ok(());
+        }
+
+        match self {
+            UnusedColumns::Ignore => Ok(()),
+            UnusedColumns::Deny => Err(Error::unused_columns(columns)),
+            UnusedColumns::Warn(callback) => {
+                callback(&Error::unused_columns(columns));
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Debug for UnusedColumns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnusedColumns::Ignore => f.write_str("Ignore"),
+            UnusedColumns::Deny => f.write_str("Deny"),
+            UnusedColumns::Warn(_) => f.write_str("Warn(..)"),
+        }
+    }
+}
+
+/// Options for how rows are deserialized, used with [`from_row_with`](crate::from_row_with)
+/// and [`from_row_ref_with`](crate::from_row_ref_with).
+///
+/// Column names are matched to struct fields after, in this order, stripping table prefixes,
+/// applying the user closure or rename rule, and ignoring case:
+///
+/// ```
+/// use serde_sqlx::{DeserializeOptions, RenameRule};
+///
+/// let options = DeserializeOptions::new()
+///     .strip_table_prefix(true)
+///     .rename_columns(RenameRule::SnakeCase)
+///     .case_insensitive(true);
+/// ```
+#[derive(Clone, Default)]
+pub struct DeserializeOptions {
+    pub(crate) case_insensitive: bool,
+    pub(crate) rename_rule: Option<RenameRule>,
+    pub(crate) strip_table_prefix: bool,
+    pub(crate) map_column_name: Option<Arc<MapColumnName>>,
+    pub(crate) unused_columns: UnusedColumns,
+    pub(crate) positional_rows: bool,
+    pub(crate) option_key_column: Option<String>,
+}
+
+impl DeserializeOptions {
+    /// The options used by [`from_row`](crate::from_row), column names are used as they are
+    pub const fn new() -> Self {
+        DeserializeOptions {
+            case_insensitive: false,
+            rename_rule: None,
+            strip_table_prefix: false,
+            map_column_name: None,
+            unused_columns: UnusedColumns::Ignore,
+            positional_rows: false,
+            option_key_column: None,
+        }
+    }
+
+    /// Matches column names to struct fields regardless of case, e.g. the `userid` column that
+    /// Postgres returns for `SELECT user_id AS userId` to a `userId` field.
+    ///
+    /// Only applies to structs, maps are keyed by the column names as they are.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Converts column names to a case convention before matching them
+    pub fn rename_columns(mut self, rule: RenameRule) -> Self {
+        self.rename_rule = Some(rule);
+        self
+    }
+
+    /// Strips everything up to the last `.` of column names, e.g. `users.id` becomes `id`.
+    ///
+    /// Nested structs can then only be built from `__` prefixes, such as `author__id`.
+    pub fn strip_table_prefix(mut self, strip: bool) -> Self {
+        self.strip_table_prefix = strip;
+        self
+    }
+
+    /// Maps column names with a closure, which replaces the rename rule if there is one
+    pub fn map_column_names<F>(mut self, map: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.map_column_name = Some(Arc::new(map));
+        self
+    }
+
+    /// Whether to error or warn when a struct doesn't have a field for every column, or a tuple
+    /// has fewer elements than the row has columns. This catches `SELECT *` queries that return
+    /// more than expected, e.g. after a migration.
+    ///
+    /// Structs with `#[serde(flatten)]` fields take every column, so they are never checked.
+    pub fn unused_columns(mut self, unused_columns: UnusedColumns) -> Self {
+        self.unused_columns = unused_columns;
+        self
+    }
+
+    /// Gives rows of several columns to types that accept anything, such as untagged enums and
+    /// `serde_json::Value`, as a sequence of values in column order instead of a map keyed by
+    /// column name. Untagged enums then match tuple variants rather than struct variants.
+    ///
+    /// A row of one column is that column's value either way.
+    pub fn positional_rows(mut self, positional: bool) -> Self {
+        self.positional_rows = positional;
+        self
+    }
+
+    /// Decides whether a whole row deserialized as an `Option` is `None` by this column alone,
+    /// e.g. the primary key of the right side of a LEFT JOIN, instead of by all of its columns
+    /// being NULL. The name is matched as the database returns it.
+    ///
+    /// Without a key column, any column that isn't NULL makes the row `Some`, including ones
+    /// that aren't fields of the struct, such as a column of the left side of the join.
+    pub fn option_key_column(mut self, column: impl Into<String>) -> Self {
+        self.option_key_column = Some(column.into());
+        self
+    }
+
+    /// The name of a column without its table prefix, if those are stripped
+    pub(crate) fn column_name<'a>(&self, name: &'a str) -> &'a str {
+        match name.rsplit_once('.') {
+            Some((_table, column)) if self.strip_table_prefix => column,
+            _ => name,
+        }
+    }
+
+    /// The key a column, or the prefix of nested columns, is given when deserializing a row as a
+    /// map or struct. Struct `fields` are borrowed for case insensitive matching when known.
+    pub(crate) fn field_key<'a>(
+        &self,
+        name: &'a str,
+        fields: Option<&'static [&'static str]>,
+    ) -> Cow<'a, str> {
+        let mut key = Cow::Borrowed(name);
+
+        if let Some(map) = &self.map_column_name {
+            key = Cow::Owned(map(&key));
+        } else if let Some(rule) = self.rename_rule {
+            key = Cow::Owned(rule.apply(&key));
+        }
+
+        if let (true, Some(fields)) = (self.case_insensitive, fields) {
+            let field = fields.iter().find(|field| field.eq_ignore_ascii_case(&key));
+            if let Some(field) = field {
+                key = Cow::Borrowed(field);
+            }
+        }
+
+        key
+    }
+}
+
+impl fmt::Debug for DeserializeOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeserializeOptions")
+            .field("case_insensitive", &self.case_insensitive)
+            .field("rename_rule", &self.rename_rule)
+            .field("strip_table_prefix", &self.strip_table_prefix)
+            .field("map_column_name", &self.map_column_name.is_some())
+            .field("unused_columns", &self.unused_columns)
+            .field("positional_rows", &self.positional_rows)
+            .field("option_key_column", &self.option_key_column)
+            .finish()
+    }
+}
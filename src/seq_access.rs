@@ -1,34 +1,27 @@
-use serde::de::{value::Error as DeError, DeserializeSeed, SeqAccess};
-use serde::ser::Error as _;
-use sqlx::Row;
+use serde::de::{DeserializeSeed, SeqAccess};
 
 use crate::databases::Database;
-use crate::deserializers::{RowDeserializer, ValueDeserializer};
+use crate::deserializers::RowDeserializer;
+use crate::error::Error;
 
 pub(crate) struct RowSeqAccess<'a, DB: Database> {
     pub(crate) deserializer: RowDeserializer<'a, DB>,
     pub(crate) num_cols: usize,
 }
 
-impl<'de, 'a, DB: Database> SeqAccess<'de> for RowSeqAccess<'a, DB>
+impl<'de, DB: Database> SeqAccess<'de> for RowSeqAccess<'de, DB>
 where
     usize: sqlx::ColumnIndex<<DB as sqlx::Database>::Row>,
 {
-    type Error = DeError;
+    type Error = Error;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
     where
         T: DeserializeSeed<'de>,
     {
         if self.deserializer.index < self.num_cols {
-            let value = self
-                .deserializer
-                .row
-                .try_get_raw(self.deserializer.index)
-                .map_err(DeError::custom)?;
-
             // Create a ValueDeserializer for the current column.
-            let value_deserializer = ValueDeserializer::<'_, DB>::new(value);
+            let value_deserializer = self.deserializer.value_deserializer()?;
 
             self.deserializer.index += 1;
 
@@ -0,0 +1,56 @@
+//! Postgres geometric types (`POINT`, `LINE`, `LSEG`, `BOX`, `PATH`, `POLYGON`, `CIRCLE`).
+//!
+//! Geometric values also deserialize into tuples in the order of these fields, e.g. a point
+//! into `(f64, f64)`, and those made of points into a sequence of them, e.g. a polygon into
+//! `Vec<(f64, f64)>`.
+
+use serde::Deserialize;
+
+/// A `POINT`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A `LINE`, of the equation `ax + by + c = 0`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct Line {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+}
+
+/// An `LSEG`, a finite line segment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct LineSegment {
+    pub start: Point,
+    pub end: Point,
+}
+
+/// A `BOX`. Postgres stores any two opposite corners as the upper right and lower left ones
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct Rect {
+    pub high: Point,
+    pub low: Point,
+}
+
+/// A `PATH`, which is open or closed like a polygon
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Path {
+    pub closed: bool,
+    pub points: Vec<Point>,
+}
+
+/// A `POLYGON`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Polygon {
+    pub points: Vec<Point>,
+}
+
+/// A `CIRCLE`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct Circle {
+    pub center: Point,
+    pub radius: f64,
+}
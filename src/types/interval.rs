@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+/// A Postgres `INTERVAL`, exactly as stored.
+///
+/// Months and days have no fixed length, so they are kept apart from the microseconds. An
+/// interval can also be deserialized into an ISO-8601 `String`, or, when it has no months, into
+/// `std::time::Duration`, `chrono::Duration` or `time::Duration`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
@@ -0,0 +1,11 @@
+//! Ready-made types to deserialize database values into.
+
+pub mod geo;
+
+mod interval;
+mod range;
+mod vector;
+
+pub use interval::Interval;
+pub use range::PgRange;
+pub use vector::{SparseVector, Vector};
@@ -0,0 +1,56 @@
+use std::ops::{Bound, RangeBounds};
+
+use serde::Deserialize;
+
+/// A Postgres range (`INT4RANGE`, `INT8RANGE`, `NUMRANGE`, `TSRANGE`, `TSTZRANGE`, `DATERANGE`).
+///
+/// Unbounded ends are `None`. A multirange deserializes into a `Vec<PgRange<T>>`.
+///
+/// ```ignore
+/// let range: PgRange<i32> = serde_sqlx::from_row(row)?; // SELECT '[1,10)'::INT4RANGE
+/// assert_eq!(range.start, Some(1));
+/// assert!(range.contains(&5));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct PgRange<T> {
+    pub start: Option<T>,
+    pub end: Option<T>,
+    pub start_inclusive: bool,
+    pub end_inclusive: bool,
+    /// Empty ranges such as `'empty'::INT4RANGE` have neither bounds nor any values
+    #[serde(default)]
+    pub empty: bool,
+}
+
+impl<T> PgRange<T> {
+    /// Whether the range contains no values at all
+    pub fn is_empty(&self) -> bool {
+        self.empty
+    }
+}
+
+impl<T> RangeBounds<T> for PgRange<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        match &self.start {
+            Some(start) if self.start_inclusive => Bound::Included(start),
+            Some(start) => Bound::Excluded(start),
+            None => Bound::Unbounded,
+        }
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        match &self.end {
+            Some(end) if self.end_inclusive => Bound::Included(end),
+            Some(end) => Bound::Excluded(end),
+            None => Bound::Unbounded,
+        }
+    }
+
+    fn contains<U>(&self, item: &U) -> bool
+    where
+        T: PartialOrd<U>,
+        U: ?Sized + PartialOrd<T>,
+    {
+        !self.empty && (self.start_bound(), self.end_bound()).contains(item)
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+
+use serde::{
+    de::{SeqAccess, Unexpected, Visitor},
+    Deserialize, Deserializer,
+};
+
+/// A pgvector `sparsevec`, the `values` of a vector of `dim` elements that aren't zero.
+///
+/// The `indices` of the values count from zero, unlike the text form of a `sparsevec`. A
+/// sparse vector can also be deserialized into a dense `Vec<f32>` or `[f32; N]`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct SparseVector {
+    pub indices: Vec<u32>,
+    pub values: Vec<f32>,
+    pub dim: u32,
+}
+
+/// A dense vector, from a sequence such as a pgvector `vector`, or from its text, e.g.
+/// `[1,2.5,-3]`.
+///
+/// sqlx doesn't know the column type of a MySQL 9 `VECTOR`, so those are selected as text with
+/// `VECTOR_TO_STRING(column)` and read with this.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Vector(pub Vec<f32>);
+
+impl<'de> Deserialize<'de> for Vector {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(VectorVisitor)
+    }
+}
+
+struct VectorVisitor;
+
+impl<'de> Visitor<'de> for VectorVisitor {
+    type Value = Vector;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of numbers, or text like `[1,2.5,-3]`")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Vector(values))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let invalid = || E::invalid_value(Unexpected::Str(v), &self);
+
+        let inner = v
+            .trim()
+            .strip_prefix('[')
+            .and_then(|v| v.strip_suffix(']'))
+            .ok_or_else(invalid)?;
+        if inner.trim().is_empty() {
+            return Ok(Vector(Vec::new()));
+        }
+
+        let values = inner
+            .split(',')
+            .map(|value| value.trim().parse().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?;
+        Ok(Vector(values))
+    }
+}
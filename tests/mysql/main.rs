@@ -16,6 +16,8 @@ mod test_12_struct;
 mod test_13_enums;
 mod test_14_chrono;
 mod test_15_json_value;
+mod test_16_decimal;
+mod test_17_vectors;
 
 #[allow(unused)]
 pub async fn fetch_one<T: for<'de> serde::Deserialize<'de>>(query: &str) -> anyhow::Result<T> {
@@ -36,3 +36,39 @@ async fn test_enum_inside_struct() {
         }
     );
 }
+
+#[tokio::test]
+async fn test_enum_from_an_integer_code() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Priority {
+        Low,
+        Medium,
+        High,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Status {
+        #[serde(rename = "1")]
+        Open,
+        #[serde(rename = "200")]
+        Closed,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Ticket {
+        priority: Priority,
+        status: Status,
+    }
+
+    let ticket: Ticket =
+        fetch_one("SELECT CAST(2 AS SIGNED) AS priority, CAST(200 AS UNSIGNED) AS status")
+            .await
+            .unwrap();
+    assert_eq!(
+        ticket,
+        Ticket {
+            priority: Priority::High,
+            status: Status::Closed,
+        }
+    );
+}
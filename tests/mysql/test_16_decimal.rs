@@ -0,0 +1,31 @@
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+
+use crate::fetch_one;
+
+#[tokio::test]
+async fn decimal_as_decimal_is_exact() {
+    let row: Decimal = fetch_one("SELECT CAST(12345678901234567.89 AS DECIMAL(30, 2)) AS value")
+        .await
+        .unwrap();
+    assert_eq!(row, Decimal::from_str("12345678901234567.89").unwrap());
+}
+
+#[tokio::test]
+async fn decimal_as_string_is_exact() {
+    // Outside the range of Decimal
+    let row: String =
+        fetch_one("SELECT CAST('123456789012345678901234567890.12345' AS DECIMAL(65, 5)) AS value")
+            .await
+            .unwrap();
+    assert_eq!(row, "123456789012345678901234567890.12345");
+}
+
+#[tokio::test]
+async fn decimal_as_f64_opt() {
+    let row: Option<f64> = fetch_one("SELECT CAST(1.25 AS DECIMAL(4, 2)) AS value")
+        .await
+        .unwrap();
+    assert_eq!(row, Some(1.25));
+}
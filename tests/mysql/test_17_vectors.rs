@@ -0,0 +1,42 @@
+use serde::Deserialize;
+use serde_sqlx::types::Vector;
+
+use crate::fetch_one;
+
+/// sqlx doesn't know the column type of a MySQL 9 VECTOR, so they are selected with
+/// `VECTOR_TO_STRING`, which gives the same text as these literals
+#[tokio::test]
+async fn vector_text_into_fields() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Document {
+        id: i32,
+        embedding: Vector,
+        empty: Vector,
+        title: String,
+    }
+
+    let row: Document = fetch_one(
+        "SELECT 1 AS id, '[1.00000e+00,2.50000e+00,-3.00000e+00]' AS embedding,
+            '[]' AS empty, '[not a vector]' AS title",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        row,
+        Document {
+            id: 1,
+            embedding: Vector(vec![1.0, 2.5, -3.0]),
+            empty: Vector(vec![]),
+            title: "[not a vector]".to_owned(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn text_that_isnt_a_vector_is_an_error() {
+    let err = fetch_one::<Vector>("SELECT '[not a vector]' AS embedding")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not a vector"), "{err}");
+}
@@ -1,5 +1,5 @@
-use sqlx::{postgres::PgPoolOptions, PgPool};
-use tokio::sync::OnceCell;
+use sqlx::{postgres::PgPoolOptions, Connection, PgConnection, PgPool};
+use std::cell::OnceCell;
 
 mod test_01_strings;
 mod test_02_bool;
@@ -14,6 +14,31 @@ mod test_10_same_type_columns_into_hashmap;
 mod test_11_record_with_flatten;
 mod test_12_structs_from_json;
 mod test_13_struct;
+mod test_14_errors;
+mod test_15_query_as;
+mod test_16_borrowed;
+mod test_17_pg_array_fields;
+mod test_18_pg_multidim_arrays;
+mod test_19_pg_composites;
+mod test_20_pg_ranges;
+mod test_21_numeric;
+mod test_22_enums;
+mod test_23_timestamps;
+mod test_24_intervals;
+mod test_25_column_names;
+mod test_26_nested_prefixes;
+mod test_27_unused_columns;
+mod test_28_tagged_enums;
+mod test_29_untagged_enums;
+mod test_30_integer_enums;
+mod test_31_optional_rows;
+mod test_32_hstore;
+mod test_33_network;
+mod test_34_geometry;
+#[cfg(feature = "postgis")]
+mod test_35_postgis;
+mod test_36_vectors;
+mod test_37_simple_query;
 
 #[allow(unused)]
 pub async fn fetch_one<T: for<'de> serde::Deserialize<'de>>(query: &str) -> anyhow::Result<T> {
@@ -50,13 +75,69 @@ pub async fn fetch_optional<T: for<'de> serde::Deserialize<'de>>(
         .map_err(Into::into)
 }
 
-async fn conn() -> PgPool {
-    static CONN: OnceCell<PgPool> = OnceCell::const_new();
+/// Runs `schema` on a fresh connection before fetching all rows with `query`. Types and tables
+/// created in `pg_temp` only live as long as that connection, so tests can't interfere
+#[allow(unused)]
+pub async fn fetch_all_with_schema<T: for<'de> serde::Deserialize<'de>>(
+    schema: &str,
+    query: &str,
+) -> anyhow::Result<Vec<T>> {
+    let conn_string = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let mut conn = PgConnection::connect(&conn_string).await.unwrap();
+
+    sqlx::raw_sql(schema).execute(&mut conn).await.unwrap();
+
+    let row = sqlx::query(query).fetch_all(&mut conn).await.unwrap();
+    let result: Result<Vec<_>, _> = row
+        .into_iter()
+        .map(serde_sqlx::from_row::<sqlx::Postgres, _>)
+        .collect();
+
+    result.map_err(Into::into)
+}
+
+/// Creates types in `pg_temp` with the input and output functions of BYTEA, which are sent as
+/// the bytes they are given. Extension types, e.g. PostGIS' `geometry`, are known by name, so
+/// their binary formats can be tested this way without installing the extension
+#[allow(unused)]
+pub fn bytea_types_schema(names: &[&str]) -> String {
+    names
+        .iter()
+        .map(|name| {
+            format!(
+                "CREATE TYPE pg_temp.{name};
+                CREATE FUNCTION pg_temp.{name}_in(cstring) RETURNS pg_temp.{name}
+                    AS 'byteain' LANGUAGE internal IMMUTABLE STRICT;
+                CREATE FUNCTION pg_temp.{name}_out(pg_temp.{name}) RETURNS cstring
+                    AS 'byteaout' LANGUAGE internal IMMUTABLE STRICT;
+                CREATE FUNCTION pg_temp.{name}_recv(internal) RETURNS pg_temp.{name}
+                    AS 'bytearecv' LANGUAGE internal IMMUTABLE STRICT;
+                CREATE FUNCTION pg_temp.{name}_send(pg_temp.{name}) RETURNS BYTEA
+                    AS 'byteasend' LANGUAGE internal IMMUTABLE STRICT;
+                CREATE TYPE pg_temp.{name} (
+                    INPUT = pg_temp.{name}_in,
+                    OUTPUT = pg_temp.{name}_out,
+                    RECEIVE = pg_temp.{name}_recv,
+                    SEND = pg_temp.{name}_send
+                );"
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Every `#[tokio::test]` runs on its own thread and runtime, and a connection can't outlive the
+/// runtime it was opened on, so each test thread has a pool of its own
+pub async fn conn() -> PgPool {
+    thread_local! {
+        static POOL: OnceCell<PgPool> = const { OnceCell::new() };
+    }
 
-    async fn init() -> PgPool {
-        let conn_string = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-        PgPoolOptions::new().connect(&conn_string).await.unwrap()
+    if let Some(pool) = POOL.with(|pool| pool.get().cloned()) {
+        return pool;
     }
 
-    CONN.get_or_init(init).await.clone()
+    let conn_string = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPoolOptions::new().connect(&conn_string).await.unwrap();
+    POOL.with(|cell| cell.get_or_init(|| pool).clone())
 }
@@ -1,4 +1,4 @@
-use crate::fetch_all;
+use crate::{fetch_all, fetch_all_with_schema};
 use serde_json::Value as JsValue;
 
 #[tokio::test]
@@ -61,3 +61,135 @@ async fn pg_arr_of_jsonb_as_vec_of_jsvalue() {
     .unwrap();
     assert_eq!(rows, vec![vec![serde_json::json!(1), serde_json::json!(2)]]);
 }
+
+#[tokio::test]
+async fn pg_arr_of_int2_and_int8() {
+    let rows: Vec<Vec<i16>> = fetch_all("SELECT ARRAY[1, -2]::INT2[]").await.unwrap();
+    assert_eq!(rows, vec![vec![1, -2]]);
+
+    let err = fetch_all::<Vec<i64>>("SELECT ARRAY[9007199254740993, NULL]::INT8[]")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("unexpected NULL"), "{err}");
+
+    let rows: Vec<Vec<Option<i64>>> = fetch_all("SELECT ARRAY[9007199254740993, NULL]::INT8[]")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![vec![Some(9007199254740993), None]]);
+}
+
+#[tokio::test]
+async fn pg_arr_of_floats() {
+    let rows: Vec<Vec<f32>> = fetch_all("SELECT ARRAY[1.5, -0.25]::FLOAT4[]")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![vec![1.5, -0.25]]);
+
+    let rows: Vec<Vec<f64>> = fetch_all("SELECT ARRAY[1.5, 'Infinity']::FLOAT8[]")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![vec![1.5, f64::INFINITY]]);
+}
+
+#[tokio::test]
+async fn pg_arr_of_numeric_as_vec_f64() {
+    let rows: Vec<Vec<f64>> =
+        fetch_all("SELECT ARRAY[12345.678, -0.0001, 0, 100000000]::NUMERIC[]")
+            .await
+            .unwrap();
+    assert_eq!(rows, vec![vec![12345.678, -0.0001, 0.0, 100000000.0]]);
+}
+
+#[tokio::test]
+async fn pg_arr_of_uuid() {
+    let rows: Vec<Vec<String>> =
+        fetch_all("SELECT ARRAY['a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11']::UUID[]")
+            .await
+            .unwrap();
+    assert_eq!(
+        rows,
+        vec![vec!["a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_owned()]]
+    );
+}
+
+#[tokio::test]
+async fn pg_arr_of_dates_and_timestamps() {
+    let rows: Vec<Vec<String>> = fetch_all("SELECT ARRAY['2024-02-29', '1999-12-31']::DATE[]")
+        .await
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![vec!["2024-02-29".to_owned(), "1999-12-31".to_owned()]]
+    );
+
+    let rows: Vec<Vec<String>> = fetch_all("SELECT ARRAY['2024-02-29 12:30:00.5']::TIMESTAMP[]")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![vec!["2024-02-29T12:30:00.500".to_owned()]]);
+
+    let rows: Vec<Vec<String>> = fetch_all("SELECT ARRAY['2024-02-29 12:30:00+02']::TIMESTAMPTZ[]")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![vec!["2024-02-29T10:30:00+00:00".to_owned()]]);
+}
+
+#[tokio::test]
+async fn pg_arr_of_bytea() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Bytes(#[serde(with = "serde_bytes_compat")] Vec<u8>);
+
+    mod serde_bytes_compat {
+        pub fn deserialize<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+            <&[u8]>::deserialize(d).map(<[u8]>::to_vec)
+        }
+
+        use serde::Deserialize as _;
+    }
+
+    let rows: Vec<Vec<Bytes>> = fetch_all("SELECT ARRAY['\\xDEAD', '\\x']::BYTEA[]")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![vec![Bytes(vec![0xDE, 0xAD]), Bytes(vec![])]]);
+}
+
+#[tokio::test]
+async fn pg_arr_of_char_and_bpchar() {
+    let rows: Vec<Vec<String>> = fetch_all(r#"SELECT ARRAY['a', 'b']::"char"[]"#)
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![vec!["a".to_owned(), "b".to_owned()]]);
+
+    let rows: Vec<Vec<String>> = fetch_all("SELECT ARRAY['ab', 'c']::CHAR(2)[]")
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![vec!["ab".to_owned(), "c ".to_owned()]]);
+}
+
+#[tokio::test]
+async fn pg_arr_of_interval() {
+    let rows: Vec<Vec<String>> =
+        fetch_all("SELECT ARRAY['1 day 2 hours', '30 seconds']::INTERVAL[]")
+            .await
+            .unwrap();
+    assert_eq!(rows, vec![vec!["P1DT2H".to_owned(), "PT30S".to_owned()]]);
+}
+
+#[tokio::test]
+async fn pg_arr_of_user_defined_enum() {
+    let rows: Vec<Vec<Option<String>>> = fetch_all_with_schema(
+        "CREATE TYPE pg_temp.mood AS ENUM ('happy', 'sad');",
+        "SELECT ARRAY['happy', NULL, 'sad']::mood[]",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        rows,
+        vec![vec![Some("happy".to_owned()), None, Some("sad".to_owned())]]
+    );
+}
+
+#[tokio::test]
+async fn pg_empty_arr() {
+    let rows: Vec<Vec<i16>> = fetch_all("SELECT '{}'::INT2[]").await.unwrap();
+    assert_eq!(rows, vec![Vec::<i16>::new()]);
+}
@@ -0,0 +1,102 @@
+use serde::Deserialize;
+use serde_sqlx::Error;
+
+use crate::fetch_one;
+
+#[derive(Debug, Deserialize)]
+#[allow(unused)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+fn unwrap_error<T: std::fmt::Debug>(res: anyhow::Result<T>) -> Error {
+    res.unwrap_err().downcast::<Error>().unwrap()
+}
+
+#[tokio::test]
+async fn missing_column() {
+    let err = unwrap_error(fetch_one::<User>("SELECT 1 AS id").await);
+
+    assert!(matches!(err, Error::MissingColumn { .. }), "{err:?}");
+    assert_eq!(err.column(), Some("name"));
+    assert_eq!(err.index(), None);
+}
+
+#[tokio::test]
+async fn unexpected_null_in_field() {
+    let err = unwrap_error(fetch_one::<User>("SELECT 1 AS id, NULL::TEXT AS name").await);
+
+    assert!(matches!(err, Error::UnexpectedNull { .. }), "{err:?}");
+    assert_eq!(err.column(), Some("name"));
+    assert_eq!(err.index(), Some(1));
+    assert_eq!(err.sql_type(), Some("TEXT"));
+    assert_eq!(err.rust_type(), Some("a string"));
+}
+
+#[tokio::test]
+async fn type_mismatch_in_field() {
+    let err = unwrap_error(fetch_one::<User>("SELECT 'one' AS id, 'name' AS name").await);
+
+    assert!(matches!(err, Error::TypeMismatch { .. }), "{err:?}");
+    assert_eq!(err.column(), Some("id"));
+    assert_eq!(err.index(), Some(0));
+    assert_eq!(err.sql_type(), Some("TEXT"));
+    assert_eq!(err.rust_type(), Some("i32"));
+}
+
+#[tokio::test]
+async fn json_error_in_field() {
+    #[derive(Debug, Deserialize)]
+    #[allow(unused)]
+    struct Profile {
+        age: i32,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(unused)]
+    struct Wrapper {
+        profile: Profile,
+    }
+
+    let err =
+        unwrap_error(fetch_one::<Wrapper>(r#"SELECT '{"age": "old"}'::JSONB AS profile"#).await);
+
+    assert!(matches!(err, Error::Json { .. }), "{err:?}");
+    assert_eq!(err.column(), Some("profile"));
+    assert_eq!(err.sql_type(), Some("JSONB"));
+}
+
+#[tokio::test]
+async fn converts_into_sqlx_column_decode() {
+    let err = unwrap_error(fetch_one::<User>("SELECT 1 AS id, NULL::TEXT AS name").await);
+
+    let sqlx::Error::ColumnDecode { index, source } = sqlx::Error::from(err) else {
+        panic!("expected a ColumnDecode error");
+    };
+
+    assert_eq!(index, "\"name\"");
+    assert!(matches!(
+        source.downcast_ref::<Error>(),
+        Some(Error::UnexpectedNull { .. })
+    ));
+}
+
+#[tokio::test]
+async fn decode_error_in_field() {
+    #[derive(Debug, Deserialize)]
+    #[allow(unused)]
+    struct Row {
+        id: i32,
+        record: (String, i32),
+    }
+
+    // BIT fields of anonymous records can't be read
+    let err = unwrap_error(fetch_one::<Row>("SELECT 1 AS id, ROW(B'101', 1) AS record").await);
+
+    assert!(matches!(err, Error::Decode { .. }), "{err:?}");
+    assert_eq!(err.column(), Some("record"));
+    assert_eq!(err.sql_type(), Some("RECORD"));
+    // Only the requested type would be worth reporting, and decoding doesn't know it
+    assert_eq!(err.rust_type(), None);
+}
@@ -0,0 +1,89 @@
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use serde_sqlx::Serde;
+
+use crate::conn;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: i32,
+    name: String,
+    active: bool,
+}
+
+const QUERY: &str = "
+    SELECT 1 AS id, 'alice' AS name, true AS active
+    UNION ALL
+    SELECT 2, 'bob', false
+";
+
+#[tokio::test]
+async fn query_as_fetch_all() {
+    let conn = conn().await;
+
+    let users: Vec<Serde<User>> = sqlx::query_as(QUERY).fetch_all(&conn).await.unwrap();
+    let users: Vec<User> = users.into_iter().map(Serde::into_inner).collect();
+
+    assert_eq!(
+        users,
+        vec![
+            User {
+                id: 1,
+                name: "alice".to_owned(),
+                active: true,
+            },
+            User {
+                id: 2,
+                name: "bob".to_owned(),
+                active: false,
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn query_as_fetch_stream() {
+    let conn = conn().await;
+
+    let names: Vec<String> = sqlx::query_as::<_, Serde<User>>(QUERY)
+        .fetch(&conn)
+        .map_ok(|user| user.0.name)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(names, ["alice", "bob"]);
+}
+
+#[tokio::test]
+async fn query_as_scalar_and_tuple() {
+    let conn = conn().await;
+
+    let Serde(count): Serde<i64> = sqlx::query_as("SELECT 42::INT8")
+        .fetch_one(&conn)
+        .await
+        .unwrap();
+    assert_eq!(count, 42);
+
+    let Serde(pair): Serde<(i32, String)> = sqlx::query_as("SELECT 1, 'one'")
+        .fetch_one(&conn)
+        .await
+        .unwrap();
+    assert_eq!(pair, (1, "one".to_owned()));
+}
+
+#[tokio::test]
+async fn query_as_error_is_column_decode() {
+    let conn = conn().await;
+
+    let err = sqlx::query_as::<_, Serde<User>>("SELECT 1 AS id, NULL::TEXT AS name")
+        .fetch_one(&conn)
+        .await
+        .unwrap_err();
+
+    let sqlx::Error::ColumnDecode { index, source } = err else {
+        panic!("expected a ColumnDecode error, got {err:?}");
+    };
+    assert_eq!(index, "\"name\"");
+    assert!(source.downcast_ref::<serde_sqlx::Error>().is_some());
+}
@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use sqlx::{Connection, PgConnection};
+
+/// The row has to outlive the deserialized value, so these tests fetch it themselves. They use
+/// their own connection as the shared pool's connections are tied to other tests' runtimes.
+async fn conn() -> PgConnection {
+    let conn_string = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    PgConnection::connect(&conn_string).await.unwrap()
+}
+
+#[derive(Debug, serde::Deserialize, PartialEq)]
+struct User<'a> {
+    id: i32,
+    name: &'a str,
+    #[serde(borrow)]
+    bio: Cow<'a, str>,
+    avatar: &'a [u8],
+}
+
+#[tokio::test]
+async fn struct_with_borrowed_fields() {
+    let mut conn = conn().await;
+    let row = sqlx::query(
+        "SELECT 1 AS id, 'Alice' AS name, 'Hello'::TEXT AS bio, '\\xDEAD'::BYTEA AS avatar",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+
+    let user: User = serde_sqlx::from_row_ref::<sqlx::Postgres, _>(&row).unwrap();
+    assert_eq!(
+        user,
+        User {
+            id: 1,
+            name: "Alice",
+            bio: Cow::Borrowed("Hello"),
+            avatar: &[0xDE, 0xAD],
+        }
+    );
+    assert!(matches!(user.bio, Cow::Borrowed(_)));
+}
+
+#[tokio::test]
+async fn varchar_as_borrowed_str() {
+    let mut conn = conn().await;
+    let row = sqlx::query("SELECT 'abc'::VARCHAR")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    let value: &str = serde_sqlx::from_row_ref::<sqlx::Postgres, _>(&row).unwrap();
+    assert_eq!(value, "abc");
+}
+
+#[tokio::test]
+async fn borrowed_column_names_as_keys() {
+    let mut conn = conn().await;
+    let row = sqlx::query("SELECT 'x' AS first, 'y' AS second")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    let map: HashMap<&str, &str> = serde_sqlx::from_row_ref::<sqlx::Postgres, _>(&row).unwrap();
+    assert_eq!(map, HashMap::from([("first", "x"), ("second", "y")]));
+}
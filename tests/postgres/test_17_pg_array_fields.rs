@@ -0,0 +1,123 @@
+use serde::Deserialize;
+
+use crate::{fetch_all, fetch_one};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Post {
+    id: i32,
+    tags: Vec<String>,
+}
+
+#[tokio::test]
+async fn array_as_struct_field() {
+    let rows: Vec<Post> = fetch_all(
+        "
+        SELECT 1 AS id, ARRAY['rust', 'sql'] AS tags
+        UNION ALL
+        SELECT 2, '{}'
+        ",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            Post {
+                id: 1,
+                tags: vec!["rust".to_owned(), "sql".to_owned()],
+            },
+            Post {
+                id: 2,
+                tags: vec![],
+            },
+        ]
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Scores {
+    scores: Option<Vec<Option<f64>>>,
+}
+
+#[tokio::test]
+async fn nullable_array_of_nullable_elements_as_struct_field() {
+    let rows: Vec<Scores> = fetch_all(
+        "
+        SELECT ARRAY[1.5, NULL]::FLOAT8[] AS scores
+        UNION ALL
+        SELECT NULL
+        ",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            Scores {
+                scores: Some(vec![Some(1.5), None]),
+            },
+            Scores { scores: None },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn arrays_as_tuple_elements() {
+    let row: (Vec<String>, i32, Vec<i64>) =
+        fetch_one("SELECT ARRAY['a', 'b'], 7, ARRAY[1, 2]::INT8[]")
+            .await
+            .unwrap();
+
+    assert_eq!(row, (vec!["a".to_owned(), "b".to_owned()], 7, vec![1, 2]));
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Tags(Vec<String>);
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct TaggedPost {
+    id: i32,
+    tags: Tags,
+}
+
+#[tokio::test]
+async fn array_as_newtype_field() {
+    let row: TaggedPost = fetch_one("SELECT 1 AS id, ARRAY['x'] AS tags")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        row,
+        TaggedPost {
+            id: 1,
+            tags: Tags(vec!["x".to_owned()]),
+        }
+    );
+}
+
+#[tokio::test]
+async fn array_as_whole_row_newtype() {
+    let row: Tags = fetch_one("SELECT ARRAY['x', 'y']").await.unwrap();
+
+    assert_eq!(row, Tags(vec!["x".to_owned(), "y".to_owned()]));
+}
+
+#[tokio::test]
+async fn null_element_in_non_optional_field_errors() {
+    let err = fetch_one::<Post>("SELECT 1 AS id, ARRAY['a', NULL] AS tags")
+        .await
+        .unwrap_err();
+    let err = err.downcast::<serde_sqlx::Error>().unwrap();
+
+    assert!(matches!(err, serde_sqlx::Error::UnexpectedNull { .. }));
+    assert_eq!(err.column(), Some("tags"));
+}
+
+#[tokio::test]
+async fn array_in_single_element_tuple() {
+    let row: (Vec<i32>,) = fetch_one("SELECT ARRAY[1, 2]").await.unwrap();
+
+    assert_eq!(row, (vec![1, 2],));
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::fetch_one;
+
+#[tokio::test]
+async fn two_dimensional_array_as_nested_vec() {
+    let row: Vec<Vec<i32>> = fetch_one("SELECT ARRAY[[1, 2, 3], [4, 5, 6]]")
+        .await
+        .unwrap();
+
+    assert_eq!(row, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+}
+
+#[tokio::test]
+async fn three_dimensional_array_as_nested_vec() {
+    let row: Vec<Vec<Vec<Option<String>>>> = fetch_one("SELECT ARRAY[[['a', NULL]], [['c', 'd']]]")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        row,
+        vec![
+            vec![vec![Some("a".to_owned()), None]],
+            vec![vec![Some("c".to_owned()), Some("d".to_owned())]],
+        ]
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Features {
+    id: i32,
+    matrix: [[f32; 2]; 2],
+}
+
+#[tokio::test]
+async fn two_dimensional_array_as_fixed_size_field() {
+    let row: Features = fetch_one("SELECT 1 AS id, ARRAY[[1, 0], [0, 1]]::FLOAT4[] AS matrix")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        row,
+        Features {
+            id: 1,
+            matrix: [[1.0, 0.0], [0.0, 1.0]],
+        }
+    );
+}
+
+#[tokio::test]
+async fn fixed_size_array_with_wrong_length_errors() {
+    let err =
+        fetch_one::<Features>("SELECT 1 AS id, ARRAY[[1, 0, 0], [0, 1, 0]]::FLOAT4[] AS matrix")
+            .await
+            .unwrap_err();
+
+    assert!(err.to_string().contains("invalid length 3"), "{err}");
+}
+
+#[tokio::test]
+async fn too_shallow_rust_type_errors() {
+    #[derive(Debug, Deserialize)]
+    #[allow(unused)]
+    struct Flat {
+        grid: Vec<i32>,
+    }
+
+    let err = fetch_one::<Flat>("SELECT ARRAY[[1, 2], [3, 4]] AS grid")
+        .await
+        .unwrap_err();
+    let err = err.downcast::<serde_sqlx::Error>().unwrap();
+
+    let serde_sqlx::Error::TypeMismatch { found, .. } = &err else {
+        panic!("expected a type mismatch, got {err:?}");
+    };
+    assert_eq!(found, "1-dimensional array");
+    assert_eq!(err.column(), Some("grid"));
+    assert_eq!(err.rust_type(), Some("i32"));
+}
+
+#[tokio::test]
+async fn too_deep_rust_type_errors() {
+    #[derive(Debug, Deserialize)]
+    #[allow(unused)]
+    struct Deep {
+        grid: Vec<Vec<i32>>,
+    }
+
+    let err = fetch_one::<Deep>("SELECT ARRAY[1, 2] AS grid")
+        .await
+        .unwrap_err();
+    let err = err.downcast::<serde_sqlx::Error>().unwrap();
+
+    assert!(
+        matches!(err, serde_sqlx::Error::TypeMismatch { .. }),
+        "{err:?}"
+    );
+    assert_eq!(err.column(), Some("grid"));
+}
+
+#[tokio::test]
+async fn lower_bound_is_kept_as_map_keys() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Offsets {
+        by_index: BTreeMap<i32, i32>,
+        as_vec: Vec<i32>,
+    }
+
+    // As a sequence the elements are the same, the offset just isn't visible
+    let row: Offsets = fetch_one(
+        "SELECT '[0:2]={10,20,30}'::INT4[] AS by_index, '[0:2]={10,20,30}'::INT4[] AS as_vec",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        row,
+        Offsets {
+            by_index: BTreeMap::from([(0, 10), (1, 20), (2, 30)]),
+            as_vec: vec![10, 20, 30],
+        }
+    );
+}
+
+#[tokio::test]
+async fn lower_bounds_of_every_dimension_as_nested_maps() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Schedule {
+        slots: BTreeMap<i32, BTreeMap<i32, String>>,
+    }
+
+    let row: Schedule = fetch_one("SELECT '[5:6][-1:0]={{a,b},{c,d}}'::TEXT[] AS slots")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        row.slots,
+        BTreeMap::from([
+            (
+                5,
+                BTreeMap::from([(-1, "a".to_owned()), (0, "b".to_owned())])
+            ),
+            (
+                6,
+                BTreeMap::from([(-1, "c".to_owned()), (0, "d".to_owned())])
+            ),
+        ])
+    );
+}
@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::{fetch_all_with_schema, fetch_one};
+
+const SCHEMA: &str = "
+    CREATE TYPE pg_temp.mood AS ENUM ('happy', 'sad');
+    CREATE TYPE pg_temp.address_t AS (street TEXT, city TEXT, zip INT4);
+    CREATE TYPE pg_temp.person_t AS (name TEXT, mood mood, address address_t, tags TEXT[]);
+";
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Address {
+    // Attributes are matched by name, not position
+    city: String,
+    street: String,
+    zip: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: i32,
+    address: Address,
+}
+
+#[tokio::test]
+async fn composite_as_nested_struct() {
+    let rows: Vec<User> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT 1 AS id, ROW('Main St', 'Springfield', NULL)::address_t AS address",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![User {
+            id: 1,
+            address: Address {
+                city: "Springfield".to_owned(),
+                street: "Main St".to_owned(),
+                zip: None,
+            },
+        }]
+    );
+}
+
+#[tokio::test]
+async fn composite_as_tuple() {
+    let rows: Vec<((String, String, i32),)> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT ROW('Main St', 'Springfield', 12345)::address_t",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![(("Main St".to_owned(), "Springfield".to_owned(), 12345),)]
+    );
+}
+
+#[tokio::test]
+async fn composite_as_map() {
+    let rows: Vec<HashMap<String, HashMap<String, serde_json::Value>>> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT ROW('Main St', 'Springfield', 1)::address_t AS address",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows[0]["address"],
+        HashMap::from([
+            ("street".to_owned(), serde_json::json!("Main St")),
+            ("city".to_owned(), serde_json::json!("Springfield")),
+            ("zip".to_owned(), serde_json::json!(1)),
+        ])
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Person {
+    name: String,
+    mood: String,
+    address: Option<Address>,
+    tags: Vec<String>,
+}
+
+#[tokio::test]
+async fn composite_with_nested_composite_enum_and_array_attributes() {
+    let rows: Vec<(Person,)> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT ROW('Alice', 'happy', ROW('Main St', 'Springfield', 1), ARRAY['a'])::person_t",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![(Person {
+            name: "Alice".to_owned(),
+            mood: "happy".to_owned(),
+            address: Some(Address {
+                city: "Springfield".to_owned(),
+                street: "Main St".to_owned(),
+                zip: Some(1),
+            }),
+            tags: vec!["a".to_owned()],
+        },)]
+    );
+}
+
+#[tokio::test]
+async fn array_of_composites_as_vec_of_structs() {
+    let rows: Vec<Vec<Address>> = fetch_all_with_schema(
+        SCHEMA,
+        "
+        SELECT array_agg(ROW(street, city, zip)::address_t ORDER BY zip)
+        FROM (VALUES ('A St', 'X', 1), ('B St', 'Y', 2)) AS t(street, city, zip)
+        ",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![vec![
+            Address {
+                city: "X".to_owned(),
+                street: "A St".to_owned(),
+                zip: Some(1),
+            },
+            Address {
+                city: "Y".to_owned(),
+                street: "B St".to_owned(),
+                zip: Some(2),
+            },
+        ]]
+    );
+}
+
+#[tokio::test]
+async fn array_of_composites_as_struct_field() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct City {
+        name: String,
+        addresses: Vec<Address>,
+    }
+
+    let rows: Vec<City> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT 'X' AS name, ARRAY[ROW('A St', 'X', NULL)::address_t] AS addresses",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![City {
+            name: "X".to_owned(),
+            addresses: vec![Address {
+                city: "X".to_owned(),
+                street: "A St".to_owned(),
+                zip: None,
+            }],
+        }]
+    );
+}
+
+#[tokio::test]
+async fn anonymous_row_as_tuple() {
+    let row: (i32, String, Option<f64>) =
+        fetch_one("SELECT ROW(1, 'a', NULL::FLOAT8)").await.unwrap();
+
+    assert_eq!(row, (1, "a".to_owned(), None));
+}
+
+#[tokio::test]
+async fn anonymous_row_as_struct_field_by_position() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Shape {
+        origin: Point,
+        pair: (String, bool),
+    }
+
+    let row: Shape = fetch_one("SELECT ROW(1, 2) AS origin, ROW('a', true) AS pair")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        row,
+        Shape {
+            origin: Point { x: 1, y: 2 },
+            pair: ("a".to_owned(), true),
+        }
+    );
+}
+
+#[tokio::test]
+async fn anonymous_row_as_map_uses_postgres_field_names() {
+    #[derive(Debug, Deserialize)]
+    struct Wrapper {
+        record: HashMap<String, serde_json::Value>,
+    }
+
+    let row: Wrapper = fetch_one("SELECT ROW(1, 'a'::VARCHAR) AS record")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        row.record,
+        HashMap::from([
+            ("f1".to_owned(), serde_json::json!(1)),
+            ("f2".to_owned(), serde_json::json!("a")),
+        ])
+    );
+}
+
+#[tokio::test]
+async fn array_of_anonymous_rows_as_vec_of_tuples() {
+    let row: Vec<(i32, Vec<String>)> = fetch_one(
+        "
+        SELECT array_agg(ROW(id, tags) ORDER BY id)
+        FROM (VALUES (1, ARRAY['a']), (2, ARRAY['b', 'c'])) AS t(id, tags)
+        ",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        row,
+        vec![
+            (1, vec!["a".to_owned()]),
+            (2, vec!["b".to_owned(), "c".to_owned()]),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn record_with_wrong_arity_errors() {
+    let err = fetch_one::<(i32, i32, i32)>("SELECT ROW(1, 2)")
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("invalid length 2"), "{err}");
+}
+
+#[tokio::test]
+async fn lone_composite_column_as_the_struct() {
+    let rows: Vec<Address> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT ROW('Main St', 'Springfield', 12345)::address_t AS address",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![Address {
+            city: "Springfield".to_owned(),
+            street: "Main St".to_owned(),
+            zip: Some(12345),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn anonymous_row_with_varchar_array_field() {
+    let row: (i32, Vec<String>) = fetch_one("SELECT ROW(1, ARRAY['a', 'bc']::VARCHAR[])")
+        .await
+        .unwrap();
+
+    assert_eq!(row, (1, vec!["a".to_owned(), "bc".to_owned()]));
+}
+
+#[tokio::test]
+async fn anonymous_row_with_bpchar_array_field() {
+    // CHAR(n) is padded to its length
+    let row: (Vec<String>, bool) = fetch_one("SELECT ROW(ARRAY['a', 'bc']::CHAR(2)[], true)")
+        .await
+        .unwrap();
+
+    assert_eq!(row, (vec!["a ".to_owned(), "bc".to_owned()], true));
+}
+
+#[tokio::test]
+async fn anonymous_row_with_money_field() {
+    // In cents
+    let row: (i64, i32) = fetch_one("SELECT ROW(1.5::MONEY, 1)").await.unwrap();
+
+    assert_eq!(row, (150, 1));
+}
+
+#[tokio::test]
+async fn anonymous_row_with_unsupported_field_type_errors() {
+    // BIT has oid 1560 and a binary format of its own
+    let err = fetch_one::<(String, i32)>("SELECT ROW(B'101', 1)")
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            err.downcast_ref::<serde_sqlx::Error>(),
+            Some(serde_sqlx::Error::Decode { .. })
+        ),
+        "{err:?}"
+    );
+    assert!(err.to_string().contains("oid 1560"), "{err}");
+}
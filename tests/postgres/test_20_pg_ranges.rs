@@ -0,0 +1,181 @@
+use std::ops::{Bound, RangeBounds};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use serde_sqlx::types::PgRange;
+
+use crate::fetch_one;
+
+#[tokio::test]
+async fn int_ranges() {
+    // Postgres normalizes discrete ranges to `[start,end)`
+    let range: PgRange<i32> = fetch_one("SELECT '[1,10]'::INT4RANGE").await.unwrap();
+    assert_eq!(
+        range,
+        PgRange {
+            start: Some(1),
+            end: Some(11),
+            start_inclusive: true,
+            end_inclusive: false,
+            empty: false,
+        }
+    );
+    assert!(range.contains(&10));
+    assert!(!range.contains(&11));
+
+    let range: PgRange<i64> = fetch_one("SELECT '[5000000000,)'::INT8RANGE")
+        .await
+        .unwrap();
+    assert_eq!(range.start, Some(5_000_000_000));
+    assert_eq!(range.end, None);
+    assert_eq!(range.end_bound(), Bound::Unbounded);
+}
+
+#[tokio::test]
+async fn numeric_range() {
+    let range: PgRange<f64> = fetch_one("SELECT '(1.5,2.25]'::NUMRANGE").await.unwrap();
+    assert_eq!(
+        range,
+        PgRange {
+            start: Some(1.5),
+            end: Some(2.25),
+            start_inclusive: false,
+            end_inclusive: true,
+            empty: false,
+        }
+    );
+}
+
+#[tokio::test]
+async fn date_and_timestamptz_ranges() {
+    let range: PgRange<NaiveDate> = fetch_one("SELECT '[2024-01-01,2024-02-01)'::DATERANGE")
+        .await
+        .unwrap();
+    assert_eq!(range.start, NaiveDate::from_ymd_opt(2024, 1, 1));
+    assert_eq!(range.end, NaiveDate::from_ymd_opt(2024, 2, 1));
+
+    let range: PgRange<DateTime<Utc>> =
+        fetch_one("SELECT tstzrange('2024-01-01 00:00:00+00', NULL)")
+            .await
+            .unwrap();
+    assert_eq!(
+        range.start,
+        Some("2024-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap())
+    );
+    assert_eq!(range.end, None);
+    assert!(range.start_inclusive);
+}
+
+#[tokio::test]
+async fn empty_range() {
+    let range: PgRange<i32> = fetch_one("SELECT 'empty'::INT4RANGE").await.unwrap();
+    assert!(range.is_empty());
+    assert!(!range.contains(&0));
+    assert_eq!((range.start, range.end), (None, None));
+}
+
+#[tokio::test]
+async fn range_as_bounds() {
+    let bounds: (Bound<i32>, Bound<i32>) = fetch_one("SELECT '[1,10)'::INT4RANGE").await.unwrap();
+    assert_eq!(bounds, (Bound::Included(1), Bound::Excluded(10)));
+
+    let bounds: (Bound<f64>, Bound<f64>) = fetch_one("SELECT '(,2.5]'::NUMRANGE").await.unwrap();
+    assert_eq!(bounds, (Bound::Unbounded, Bound::Included(2.5)));
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Booking {
+    id: i32,
+    during: PgRange<NaiveDate>,
+    seats: (Bound<i32>, Bound<i32>),
+    maybe: Option<PgRange<i32>>,
+}
+
+#[tokio::test]
+async fn ranges_as_fields() {
+    let booking: Booking = fetch_one(
+        "SELECT 1 AS id,
+            '[2024-03-01,2024-03-05)'::DATERANGE AS during,
+            '[1,4)'::INT4RANGE AS seats,
+            NULL::INT4RANGE AS maybe",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        booking,
+        Booking {
+            id: 1,
+            during: PgRange {
+                start: NaiveDate::from_ymd_opt(2024, 3, 1),
+                end: NaiveDate::from_ymd_opt(2024, 3, 5),
+                start_inclusive: true,
+                end_inclusive: false,
+                empty: false,
+            },
+            seats: (Bound::Included(1), Bound::Excluded(4)),
+            maybe: None,
+        }
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Span {
+    start: Option<i64>,
+    end: Option<i64>,
+    start_inclusive: bool,
+    end_inclusive: bool,
+}
+
+#[tokio::test]
+async fn range_into_own_struct() {
+    let span: Span = fetch_one("SELECT '(,100)'::INT8RANGE").await.unwrap();
+    assert_eq!(
+        span,
+        Span {
+            start: None,
+            end: Some(100),
+            start_inclusive: false,
+            end_inclusive: false,
+        }
+    );
+}
+
+#[tokio::test]
+async fn range_arrays() {
+    let ranges: Vec<PgRange<i32>> = fetch_one("SELECT ARRAY['[1,2)', 'empty']::INT4RANGE[]")
+        .await
+        .unwrap();
+    assert_eq!(ranges.len(), 2);
+    assert_eq!(ranges[0].start, Some(1));
+    assert!(ranges[1].is_empty());
+}
+
+#[tokio::test]
+async fn multiranges() {
+    let ranges: Vec<PgRange<i32>> = fetch_one("SELECT '{[1,3), [5,)}'::INT4MULTIRANGE")
+        .await
+        .unwrap();
+    assert_eq!(
+        ranges
+            .iter()
+            .map(|range| (range.start, range.end))
+            .collect::<Vec<_>>(),
+        [(Some(1), Some(3)), (Some(5), None)]
+    );
+
+    let ranges: Vec<(Bound<NaiveDate>, Bound<NaiveDate>)> =
+        fetch_one("SELECT '{}'::DATEMULTIRANGE").await.unwrap();
+    assert!(ranges.is_empty());
+
+    #[derive(Debug, Deserialize)]
+    struct Schedule {
+        slots: Vec<PgRange<i64>>,
+    }
+
+    let schedule: Schedule =
+        fetch_one("SELECT '{[1,2), [10,20)}'::INT8MULTIRANGE AS slots, 1 AS id")
+            .await
+            .unwrap();
+    assert_eq!(schedule.slots[1].start, Some(10));
+}
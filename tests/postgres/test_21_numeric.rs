@@ -0,0 +1,94 @@
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::fetch_one;
+
+#[tokio::test]
+async fn numeric_as_decimal_is_exact() {
+    let row: Decimal = fetch_one("SELECT 12345678901234567.89::NUMERIC")
+        .await
+        .unwrap();
+    assert_eq!(row, Decimal::from_str("12345678901234567.89").unwrap());
+
+    // Trailing zeros of the scale are kept
+    let row: Decimal = fetch_one("SELECT 0.10::NUMERIC(10, 2)").await.unwrap();
+    assert_eq!(row.to_string(), "0.10");
+}
+
+#[tokio::test]
+async fn numeric_as_string_is_exact() {
+    // Far outside the range of both f64 and Decimal
+    let row: String =
+        fetch_one("SELECT '-123456789012345678901234567890.000000000000000000001'::NUMERIC")
+            .await
+            .unwrap();
+    assert_eq!(row, "-123456789012345678901234567890.000000000000000000001");
+}
+
+#[tokio::test]
+async fn numeric_as_f64() {
+    let row: f64 = fetch_one("SELECT 1.25::NUMERIC").await.unwrap();
+    assert_eq!(row, 1.25);
+
+    let row: Option<f32> = fetch_one("SELECT -0.5::NUMERIC").await.unwrap();
+    assert_eq!(row, Some(-0.5));
+}
+
+#[tokio::test]
+async fn numeric_special_values() {
+    let row: f64 = fetch_one("SELECT 'NaN'::NUMERIC").await.unwrap();
+    assert!(row.is_nan());
+
+    let row: (f64, f64) = fetch_one("SELECT 'Infinity'::NUMERIC, '-Infinity'::NUMERIC")
+        .await
+        .unwrap();
+    assert_eq!(row, (f64::INFINITY, f64::NEG_INFINITY));
+
+    let row: (String, String) = fetch_one("SELECT 'NaN'::NUMERIC, '-Infinity'::NUMERIC")
+        .await
+        .unwrap();
+    assert_eq!(row, ("NaN".to_owned(), "-Infinity".to_owned()));
+
+    let err = fetch_one::<Decimal>("SELECT 'NaN'::NUMERIC")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("NaN"), "{err}");
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Invoice {
+    id: i32,
+    total: Decimal,
+    discount: Option<Decimal>,
+    approx: f64,
+    lines: Vec<Decimal>,
+}
+
+#[tokio::test]
+async fn numeric_fields_and_arrays() {
+    let invoice: Invoice = fetch_one(
+        "SELECT 1 AS id,
+            199.99::NUMERIC AS total,
+            NULL::NUMERIC AS discount,
+            199.99::NUMERIC AS approx,
+            ARRAY[0.01, 99.99]::NUMERIC[] AS lines",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        invoice,
+        Invoice {
+            id: 1,
+            total: Decimal::from_str("199.99").unwrap(),
+            discount: None,
+            approx: 199.99,
+            lines: vec![
+                Decimal::from_str("0.01").unwrap(),
+                Decimal::from_str("99.99").unwrap(),
+            ],
+        }
+    );
+}
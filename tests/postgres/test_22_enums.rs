@@ -0,0 +1,119 @@
+use serde::Deserialize;
+
+use crate::{conn, fetch_all_with_schema, fetch_one};
+
+const SCHEMA: &str = "
+    CREATE TYPE pg_temp.mood AS ENUM ('happy', 'sad', 'so-so');
+    CREATE TYPE pg_temp.checkin_t AS (day INT4, mood mood);
+";
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Mood {
+    Happy,
+    Sad,
+    #[serde(rename = "so-so")]
+    SoSo,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Person {
+    name: String,
+    mood: Mood,
+    previous: Option<Mood>,
+}
+
+#[tokio::test]
+async fn native_enum_by_itself() {
+    let rows: Vec<Mood> =
+        fetch_all_with_schema(SCHEMA, "SELECT m FROM unnest(enum_range(NULL::mood)) AS m")
+            .await
+            .unwrap();
+
+    assert_eq!(rows, vec![Mood::Happy, Mood::Sad, Mood::SoSo]);
+}
+
+#[tokio::test]
+async fn native_enum_inside_struct() {
+    let rows: Vec<Person> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT 'ann' AS name, 'sad'::mood AS mood, NULL::mood AS previous
+        UNION ALL
+        SELECT 'bob', 'happy', 'so-so'",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            Person {
+                name: "ann".to_owned(),
+                mood: Mood::Sad,
+                previous: None,
+            },
+            Person {
+                name: "bob".to_owned(),
+                mood: Mood::Happy,
+                previous: Some(Mood::SoSo),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn native_enums_in_arrays_and_composites() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Checkin {
+        day: i32,
+        mood: Mood,
+    }
+
+    let rows: Vec<(Vec<Mood>, Checkin)> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT ARRAY['happy', 'so-so']::mood[], ROW(3, 'sad')::checkin_t",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![(
+            vec![Mood::Happy, Mood::SoSo],
+            Checkin {
+                day: 3,
+                mood: Mood::Sad
+            }
+        )]
+    );
+}
+
+#[tokio::test]
+async fn text_as_enum() {
+    let mood: Mood = fetch_one("SELECT 'happy'::TEXT").await.unwrap();
+    assert_eq!(mood, Mood::Happy);
+
+    let person: Person =
+        fetch_one("SELECT 'cy' AS name, 'sad'::VARCHAR AS mood, 'happy' AS previous")
+            .await
+            .unwrap();
+    assert_eq!(person.mood, Mood::Sad);
+    assert_eq!(person.previous, Some(Mood::Happy));
+}
+
+#[tokio::test]
+async fn text_protocol_as_enum() {
+    let row = sqlx::raw_sql("SELECT 'so-so' AS mood")
+        .fetch_one(&conn().await)
+        .await
+        .unwrap();
+
+    let mood: Mood = serde_sqlx::from_row::<sqlx::Postgres, _>(row).unwrap();
+    assert_eq!(mood, Mood::SoSo);
+}
+
+#[tokio::test]
+async fn unknown_variant_errors() {
+    let err = fetch_one::<Mood>("SELECT 'angry'::TEXT").await.unwrap_err();
+    assert!(err.to_string().contains("unknown variant `angry`"), "{err}");
+}
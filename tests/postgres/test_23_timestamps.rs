@@ -0,0 +1,120 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde::Deserialize;
+use serde_sqlx::types::PgRange;
+
+use crate::fetch_one;
+
+fn naive(s: &str) -> NaiveDateTime {
+    s.parse().unwrap()
+}
+
+#[tokio::test]
+async fn timestamp_as_naive_datetime() {
+    let ts: NaiveDateTime = fetch_one("SELECT '2024-02-29 12:30:00.123456'::TIMESTAMP")
+        .await
+        .unwrap();
+    assert_eq!(ts, naive("2024-02-29T12:30:00.123456"));
+
+    // No offset is made up for a naive timestamp
+    let ts: String = fetch_one("SELECT '1999-12-31 23:59:59'::TIMESTAMP")
+        .await
+        .unwrap();
+    assert_eq!(ts, "1999-12-31T23:59:59");
+}
+
+#[tokio::test]
+async fn timestamptz_as_datetime() {
+    let ts: DateTime<Utc> = fetch_one("SELECT '2024-02-29 12:30:00+02'::TIMESTAMPTZ")
+        .await
+        .unwrap();
+    assert_eq!(ts, "2024-02-29T10:30:00Z".parse::<DateTime<Utc>>().unwrap());
+
+    let ts: DateTime<FixedOffset> = fetch_one("SELECT '2024-02-29 12:30:00+02'::TIMESTAMPTZ")
+        .await
+        .unwrap();
+    assert_eq!(
+        ts,
+        "2024-02-29T12:30:00+02:00"
+            .parse::<DateTime<FixedOffset>>()
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn date_and_time() {
+    let row: (NaiveDate, NaiveTime) = fetch_one("SELECT '2024-02-29'::DATE, '08:15:30.5'::TIME")
+        .await
+        .unwrap();
+    assert_eq!(
+        row,
+        (
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            NaiveTime::from_hms_milli_opt(8, 15, 30, 500).unwrap(),
+        )
+    );
+}
+
+#[tokio::test]
+async fn end_of_day_time() {
+    // Postgres allows 24:00:00, chrono doesn't
+    let row: (String, String) = fetch_one("SELECT '24:00:00'::TIME, '24:00:00+02'::TIMETZ")
+        .await
+        .unwrap();
+    assert_eq!(row, ("24:00:00".to_owned(), "24:00:00+02:00".to_owned()));
+
+    let err = fetch_one::<NaiveTime>("SELECT '24:00:00'::TIME")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("out of range"), "{err}");
+}
+
+#[tokio::test]
+async fn timetz_keeps_its_offset() {
+    let row: (String, String) = fetch_one("SELECT '08:15:30+05:30'::TIMETZ, '23:00:00-08'::TIMETZ")
+        .await
+        .unwrap();
+    assert_eq!(
+        row,
+        ("08:15:30+05:30".to_owned(), "23:00:00-08:00".to_owned())
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct AuditEntry {
+    id: i32,
+    created_at: NaiveDateTime,
+    synced_at: Option<DateTime<Utc>>,
+    history: Vec<NaiveDateTime>,
+}
+
+#[tokio::test]
+async fn mixed_timestamps_in_a_struct() {
+    let entry: AuditEntry = fetch_one(
+        "SELECT 1 AS id,
+            '2024-01-01 09:00:00'::TIMESTAMP AS created_at,
+            '2024-01-01 09:00:00+01'::TIMESTAMPTZ AS synced_at,
+            ARRAY['2023-12-31 00:00:00']::TIMESTAMP[] AS history",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        entry,
+        AuditEntry {
+            id: 1,
+            created_at: naive("2024-01-01T09:00:00"),
+            synced_at: Some("2024-01-01T08:00:00Z".parse().unwrap()),
+            history: vec![naive("2023-12-31T00:00:00")],
+        }
+    );
+}
+
+#[tokio::test]
+async fn timestamp_range() {
+    let range: PgRange<NaiveDateTime> =
+        fetch_one("SELECT '[2024-01-01 00:00, 2024-01-02 00:00)'::TSRANGE")
+            .await
+            .unwrap();
+    assert_eq!(range.start, Some(naive("2024-01-01T00:00:00")));
+    assert_eq!(range.end, Some(naive("2024-01-02T00:00:00")));
+}
@@ -0,0 +1,132 @@
+use serde::Deserialize;
+use serde_sqlx::types::Interval;
+
+use crate::fetch_one;
+
+#[tokio::test]
+async fn interval_as_struct_keeps_months() {
+    let interval: Interval = fetch_one("SELECT '1 year 2 months 3 days 04:05:06.5'::INTERVAL")
+        .await
+        .unwrap();
+    assert_eq!(
+        interval,
+        Interval {
+            months: 14,
+            days: 3,
+            microseconds: 14_706_500_000,
+        }
+    );
+
+    let interval: Interval = fetch_one("SELECT '1 month'::INTERVAL").await.unwrap();
+    assert_eq!(interval.months, 1);
+}
+
+#[tokio::test]
+async fn interval_as_iso_8601() {
+    let row: (String, String, String, String) = fetch_one(
+        "SELECT
+            '1 year 2 months 3 days 04:05:06.5'::INTERVAL,
+            '-1 month 1 day -00:00:00.25'::INTERVAL,
+            '90 minutes'::INTERVAL,
+            '0'::INTERVAL",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        row,
+        (
+            "P1Y2M3DT4H5M6.5S".to_owned(),
+            "P-1M1DT-0.25S".to_owned(),
+            "PT1H30M".to_owned(),
+            "PT0S".to_owned(),
+        )
+    );
+}
+
+#[tokio::test]
+async fn interval_as_std_duration() {
+    let duration: std::time::Duration = fetch_one("SELECT '1 day 00:00:01.000002'::INTERVAL")
+        .await
+        .unwrap();
+    assert_eq!(duration, std::time::Duration::new(86_401, 2_000));
+
+    let err = fetch_one::<std::time::Duration>("SELECT '-1 second'::INTERVAL")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("negative"), "{err}");
+}
+
+#[tokio::test]
+async fn own_struct_with_duration_fields_is_not_a_duration() {
+    #[derive(Debug, Deserialize)]
+    #[allow(unused)]
+    struct Elapsed {
+        secs: u64,
+        nanos: u32,
+    }
+
+    let err = fetch_one::<Elapsed>("SELECT '1 second'::INTERVAL")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("column `secs`"), "{err}");
+}
+
+#[tokio::test]
+async fn interval_as_chrono_and_time_durations() {
+    let duration: chrono::Duration = fetch_one("SELECT '-1.5 seconds'::INTERVAL").await.unwrap();
+    assert_eq!(duration, chrono::Duration::milliseconds(-1_500));
+
+    let duration: time::Duration = fetch_one("SELECT '-1.5 seconds'::INTERVAL").await.unwrap();
+    assert_eq!(duration, time::Duration::milliseconds(-1_500));
+}
+
+#[tokio::test]
+async fn interval_with_months_into_a_duration_errors() {
+    let err = fetch_one::<chrono::Duration>("SELECT '1 month'::INTERVAL")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("months"), "{err}");
+
+    let err = fetch_one::<std::time::Duration>("SELECT '1 month'::INTERVAL")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("months"), "{err}");
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Job {
+    id: i32,
+    timeout: std::time::Duration,
+    backoff: Option<chrono::Duration>,
+    period: Interval,
+    retries: Vec<time::Duration>,
+}
+
+#[tokio::test]
+async fn intervals_as_fields() {
+    let job: Job = fetch_one(
+        "SELECT 1 AS id,
+            '30 seconds'::INTERVAL AS timeout,
+            NULL::INTERVAL AS backoff,
+            '1 month'::INTERVAL AS period,
+            ARRAY['1 second', '2 seconds']::INTERVAL[] AS retries",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        job,
+        Job {
+            id: 1,
+            timeout: std::time::Duration::from_secs(30),
+            backoff: None,
+            period: Interval {
+                months: 1,
+                days: 0,
+                microseconds: 0,
+            },
+            retries: vec![time::Duration::seconds(1), time::Duration::seconds(2)],
+        }
+    );
+}
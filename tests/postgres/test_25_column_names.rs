@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_sqlx::{DeserializeOptions, RenameRule};
+
+use crate::conn;
+
+async fn fetch_one_with<T: for<'de> Deserialize<'de>>(
+    query: &str,
+    options: &DeserializeOptions,
+) -> Result<T, serde_sqlx::Error> {
+    let row = sqlx::query(query).fetch_one(&conn().await).await.unwrap();
+
+    serde_sqlx::from_row_with::<sqlx::Postgres, _>(row, options)
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Account {
+    user_id: i32,
+    display_name: String,
+}
+
+#[tokio::test]
+async fn unquoted_aliases_are_lowercased_by_postgres() {
+    let query = "SELECT 1 AS userId, 'ann' AS displayName";
+
+    let err = fetch_one_with::<Account>(query, &DeserializeOptions::new())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("missing column"), "{err}");
+
+    let options = DeserializeOptions::new().case_insensitive(true);
+    let account: Account = fetch_one_with(query, &options).await.unwrap();
+    assert_eq!(
+        account,
+        Account {
+            user_id: 1,
+            display_name: "ann".to_owned(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn snake_case_columns_into_camel_case_fields() {
+    let options = DeserializeOptions::new().rename_columns(RenameRule::CamelCase);
+    let account: Account = fetch_one_with("SELECT 2 AS user_id, 'bob' AS display_name", &options)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        account,
+        Account {
+            user_id: 2,
+            display_name: "bob".to_owned(),
+        }
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: i32,
+    created_by: String,
+}
+
+#[tokio::test]
+async fn camel_case_columns_into_snake_case_fields() {
+    let options = DeserializeOptions::new().rename_columns(RenameRule::SnakeCase);
+    let user: User = fetch_one_with(r#"SELECT 3 AS "Id", 'cy' AS "createdBy""#, &options)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        user,
+        User {
+            id: 3,
+            created_by: "cy".to_owned(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn table_prefixes_are_stripped() {
+    let options = DeserializeOptions::new()
+        .strip_table_prefix(true)
+        .case_insensitive(true);
+    let user: User = fetch_one_with(
+        r#"SELECT 4 AS "users.ID", 'dee' AS "public.users.created_by""#,
+        &options,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        user,
+        User {
+            id: 4,
+            created_by: "dee".to_owned(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn user_closure_maps_names() {
+    let options = DeserializeOptions::new()
+        .map_column_names(|name| name.strip_prefix("col_").unwrap_or(name).to_owned());
+    let user: User = fetch_one_with("SELECT 5 AS col_id, 'eve' AS col_created_by", &options)
+        .await
+        .unwrap();
+    assert_eq!(user.id, 5);
+
+    // Maps get the mapped names as keys too
+    let map: BTreeMap<String, i32> = fetch_one_with("SELECT 1 AS col_a, 2 AS col_b", &options)
+        .await
+        .unwrap();
+    assert_eq!(
+        map,
+        BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)])
+    );
+}
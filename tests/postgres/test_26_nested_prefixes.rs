@@ -0,0 +1,157 @@
+use serde::Deserialize;
+
+use crate::{fetch_all, fetch_one};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Author {
+    id: i32,
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Post {
+    id: i32,
+    title: String,
+    author: Author,
+}
+
+#[tokio::test]
+async fn dotted_prefixes() {
+    let post: Post =
+        fetch_one(r#"SELECT 1 AS id, 'Hello' AS title, 7 AS "author.id", 'ann' AS "author.name""#)
+            .await
+            .unwrap();
+
+    assert_eq!(
+        post,
+        Post {
+            id: 1,
+            title: "Hello".to_owned(),
+            author: Author {
+                id: 7,
+                name: "ann".to_owned(),
+            },
+        }
+    );
+}
+
+#[tokio::test]
+async fn double_underscore_prefixes() {
+    // Unquoted, so usable without quoting every alias
+    let post: Post =
+        fetch_one("SELECT 7 AS author__id, 2 AS id, 'ann' AS author__name, 'Hi' AS title")
+            .await
+            .unwrap();
+
+    assert_eq!(post.id, 2);
+    assert_eq!(post.author.id, 7);
+    assert_eq!(post.author.name, "ann");
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Address {
+    city: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Publisher {
+    name: String,
+    address: Address,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Book {
+    title: String,
+    publisher: Publisher,
+}
+
+#[tokio::test]
+async fn nesting_at_any_depth() {
+    let book: Book = fetch_one(
+        r#"SELECT 'Dune' AS title, 'Chilton' AS "publisher.name",
+            'Philadelphia' AS "publisher__address.city""#,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        book,
+        Book {
+            title: "Dune".to_owned(),
+            publisher: Publisher {
+                name: "Chilton".to_owned(),
+                address: Address {
+                    city: "Philadelphia".to_owned(),
+                },
+            },
+        }
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Comment {
+    id: i32,
+    author: Option<Author>,
+}
+
+#[tokio::test]
+async fn optional_nested_struct_from_left_join() {
+    let comments: Vec<Comment> = fetch_all(
+        r#"
+        SELECT c.id, a.id AS "author.id", a.name AS "author.name"
+        FROM (VALUES (1, 10), (2, NULL)) AS c(id, author_id)
+        LEFT JOIN (VALUES (10, 'ann')) AS a(id, name) ON a.id = c.author_id
+        ORDER BY c.id
+        "#,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        comments,
+        vec![
+            Comment {
+                id: 1,
+                author: Some(Author {
+                    id: 10,
+                    name: "ann".to_owned(),
+                }),
+            },
+            Comment {
+                id: 2,
+                author: None,
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn optional_nested_struct_with_some_null_columns() {
+    let err = fetch_one::<Comment>(r#"SELECT 1 AS id, 10 AS "author.id", NULL AS "author.name""#)
+        .await
+        .unwrap_err();
+
+    // Not all of the author's columns are NULL, so it isn't None
+    assert!(err.to_string().contains("author.name"), "{err}");
+}
+
+#[tokio::test]
+async fn prefixes_that_are_not_fields_stay_columns() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Row {
+        #[serde(rename = "total.count")]
+        total_count: i64,
+        id: i32,
+    }
+
+    let row: Row = fetch_one(r#"SELECT 3::INT8 AS "total.count", 1 AS id"#)
+        .await
+        .unwrap();
+    assert_eq!(
+        row,
+        Row {
+            total_count: 3,
+            id: 1,
+        }
+    );
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+use serde_sqlx::{DeserializeOptions, UnusedColumns};
+
+use crate::conn;
+
+async fn fetch_one_with<T: for<'de> Deserialize<'de>>(
+    query: &str,
+    options: &DeserializeOptions,
+) -> Result<T, serde_sqlx::Error> {
+    let row = sqlx::query(query).fetch_one(&conn().await).await.unwrap();
+
+    serde_sqlx::from_row_with::<sqlx::Postgres, _>(row, options)
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+#[tokio::test]
+async fn unused_columns_are_ignored_by_default() {
+    let user: User = fetch_one_with(
+        "SELECT 1 AS id, 'ann' AS name, 'x' AS extra",
+        &DeserializeOptions::new(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        user,
+        User {
+            id: 1,
+            name: "ann".to_owned(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn deny_names_every_unused_column() {
+    let options = DeserializeOptions::new().unused_columns(UnusedColumns::Deny);
+    let err = fetch_one_with::<User>(
+        "SELECT 'x' AS extra, 1 AS id, 'ann' AS name, 2 AS other",
+        &options,
+    )
+    .await
+    .unwrap_err();
+
+    match &err {
+        serde_sqlx::Error::UnusedColumns { columns, .. } => {
+            assert_eq!(columns, &["extra", "other"]);
+        }
+        other => panic!("expected unused columns, got {other:?}"),
+    }
+    assert!(
+        err.to_string().contains("unused columns: `extra`, `other`"),
+        "{err}"
+    );
+
+    let user: User = fetch_one_with("SELECT 1 AS id, 'ann' AS name", &options)
+        .await
+        .unwrap();
+    assert_eq!(user.id, 1);
+}
+
+#[tokio::test]
+async fn warn_reports_and_carries_on() {
+    let warnings = Arc::new(Mutex::new(Vec::new()));
+    let options = DeserializeOptions::new().unused_columns(UnusedColumns::warn({
+        let warnings = Arc::clone(&warnings);
+        move |err| warnings.lock().unwrap().push(err.to_string())
+    }));
+
+    let user: User = fetch_one_with("SELECT 1 AS id, 'ann' AS name, 'x' AS extra", &options)
+        .await
+        .unwrap();
+
+    assert_eq!(user.name, "ann");
+    let warnings = warnings.lock().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("`extra`"), "{}", warnings[0]);
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Post {
+    id: i32,
+    author: Author,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Author {
+    name: String,
+}
+
+#[tokio::test]
+async fn nested_structs_report_full_column_names() {
+    let options = DeserializeOptions::new().unused_columns(UnusedColumns::Deny);
+    let err = fetch_one_with::<Post>(
+        r#"SELECT 1 AS id, 'ann' AS "author.name", 'a@b.c' AS "author.email""#,
+        &options,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(
+        err.to_string().contains("unused columns: `author.email`"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn tuples_shorter_than_the_row() {
+    let options = DeserializeOptions::new().unused_columns(UnusedColumns::Deny);
+    let query = "SELECT 1 AS a, 2 AS b, 3 AS c";
+
+    let err = fetch_one_with::<(i32, i32)>(query, &options)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("unused columns: `c`"), "{err}");
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Pair(i32, i32);
+    let err = fetch_one_with::<Pair>(query, &options).await.unwrap_err();
+    assert!(err.to_string().contains("unused columns: `c`"), "{err}");
+
+    let pair: Pair = fetch_one_with("SELECT 1 AS a, 2 AS b", &options)
+        .await
+        .unwrap();
+    assert_eq!(pair, Pair(1, 2));
+
+    let triple: (i32, i32, i32) = fetch_one_with(query, &options).await.unwrap();
+    assert_eq!(triple, (1, 2, 3));
+}
+
+#[derive(Debug, Deserialize)]
+struct WithRest {
+    id: i32,
+    #[serde(flatten)]
+    rest: HashMap<String, i32>,
+}
+
+#[tokio::test]
+async fn maps_and_flattened_structs_use_every_column() {
+    let options = DeserializeOptions::new().unused_columns(UnusedColumns::Deny);
+    let query = "SELECT 1 AS id, 2 AS a, 3 AS b";
+
+    let with_rest: WithRest = fetch_one_with(query, &options).await.unwrap();
+    assert_eq!(with_rest.id, 1);
+    assert_eq!(with_rest.rest.len(), 2);
+
+    let map: HashMap<String, i32> = fetch_one_with(query, &options).await.unwrap();
+    assert_eq!(map.len(), 3);
+}
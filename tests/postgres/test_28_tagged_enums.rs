@@ -0,0 +1,246 @@
+use serde::Deserialize;
+
+use crate::fetch_all;
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum PaymentMethod {
+    Card {
+        last4: String,
+        expiry: Option<String>,
+    },
+    Bank {
+        iban: String,
+    },
+    Cash,
+}
+
+#[tokio::test]
+async fn internally_tagged_from_variant_columns() {
+    let rows: Vec<PaymentMethod> = fetch_all(
+        "
+        SELECT 'card' AS kind, '4242' AS last4, '12/30' AS expiry, NULL::TEXT AS iban
+        UNION ALL SELECT 'bank', NULL, NULL, 'DE89 3704'
+        UNION ALL SELECT 'cash', NULL, NULL, NULL
+        ",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        [
+            PaymentMethod::Card {
+                last4: "4242".to_owned(),
+                expiry: Some("12/30".to_owned()),
+            },
+            PaymentMethod::Bank {
+                iban: "DE89 3704".to_owned(),
+            },
+            PaymentMethod::Cash,
+        ]
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Card {
+    last4: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Bank {
+    iban: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum WithPayload {
+    Card { id: i32, payload: Card },
+    Bank { id: i32, payload: Bank },
+}
+
+#[tokio::test]
+async fn internally_tagged_with_a_jsonb_payload() {
+    let rows: Vec<WithPayload> = fetch_all(
+        r#"
+        SELECT 1 AS id, 'card' AS kind, '{"last4": "4242"}'::JSONB AS payload
+        UNION ALL SELECT 2, 'bank', '{"iban": "DE89"}'::JSONB
+        "#,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        [
+            WithPayload::Card {
+                id: 1,
+                payload: Card {
+                    last4: "4242".to_owned()
+                },
+            },
+            WithPayload::Bank {
+                id: 2,
+                payload: Bank {
+                    iban: "DE89".to_owned()
+                },
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn internally_tagged_by_a_native_enum() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(tag = "kind", rename_all = "snake_case")]
+    enum Method {
+        Card { last4: String },
+        Bank { iban: String },
+    }
+
+    let rows: Vec<Method> = crate::fetch_all_with_schema(
+        "CREATE TYPE pg_temp.payment_kind AS ENUM ('card', 'bank');",
+        "
+        SELECT 'card'::pg_temp.payment_kind AS kind, '4242' AS last4, NULL AS iban
+        UNION ALL SELECT 'bank', NULL, 'DE89'
+        ",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        [
+            Method::Card {
+                last4: "4242".to_owned()
+            },
+            Method::Bank {
+                iban: "DE89".to_owned()
+            },
+        ]
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+enum Adjacent {
+    Card(Card),
+    Bank(Bank),
+    Cash,
+}
+
+#[tokio::test]
+async fn adjacently_tagged_with_a_jsonb_payload() {
+    let rows: Vec<Adjacent> = fetch_all(
+        r#"
+        SELECT 'card' AS kind, '{"last4": "4242"}'::JSONB AS data
+        UNION ALL SELECT 'bank', '{"iban": "DE89"}'::JSONB
+        UNION ALL SELECT 'cash', NULL
+        "#,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        [
+            Adjacent::Card(Card {
+                last4: "4242".to_owned()
+            }),
+            Adjacent::Bank(Bank {
+                iban: "DE89".to_owned()
+            }),
+            Adjacent::Cash,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn adjacently_tagged_with_prefixed_columns() {
+    let rows: Vec<Adjacent> = fetch_all(
+        r#"
+        SELECT 'card' AS kind, '4242' AS "data.last4", NULL AS "data.iban"
+        UNION ALL SELECT 'bank', NULL, 'DE89'
+        "#,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        [
+            Adjacent::Card(Card {
+                last4: "4242".to_owned()
+            }),
+            Adjacent::Bank(Bank {
+                iban: "DE89".to_owned()
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn unknown_tags_are_errors() {
+    let err = fetch_all::<PaymentMethod>("SELECT 'crypto' AS kind, NULL AS last4")
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string().contains("unknown variant `crypto`"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn internally_tagged_unit_variant_from_the_tag_alone() {
+    let rows: Vec<PaymentMethod> = fetch_all("SELECT 'cash' AS kind").await.unwrap();
+
+    assert_eq!(rows, [PaymentMethod::Cash]);
+}
+
+#[tokio::test]
+async fn internally_tagged_from_a_lone_jsonb_column() {
+    let rows: Vec<PaymentMethod> =
+        fetch_all(r#"SELECT '{"kind": "bank", "iban": "DE89"}'::JSONB AS method"#)
+            .await
+            .unwrap();
+
+    assert_eq!(
+        rows,
+        [PaymentMethod::Bank {
+            iban: "DE89".to_owned()
+        }]
+    );
+}
+
+#[tokio::test]
+async fn internally_tagged_numeric_fields() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(tag = "kind", rename_all = "snake_case")]
+    enum Charge {
+        Fee { amount: f64 },
+        Exact { amount: String },
+    }
+
+    // Variant fields are buffered by serde, which keeps the exact NUMERIC as a string that a
+    // float can't be read from, so floats need a FLOAT8 column
+    let err = fetch_all::<Charge>("SELECT 'fee' AS kind, 1.5::NUMERIC AS amount")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("found string \"1.5\""), "{err}");
+
+    let rows: Vec<Charge> = fetch_all("SELECT 'fee' AS kind, 1.5::NUMERIC::FLOAT8 AS amount")
+        .await
+        .unwrap();
+    assert_eq!(rows, [Charge::Fee { amount: 1.5 }]);
+
+    let rows: Vec<Charge> = fetch_all("SELECT 'exact' AS kind, 2.25::NUMERIC AS amount")
+        .await
+        .unwrap();
+    assert_eq!(
+        rows,
+        [Charge::Exact {
+            amount: "2.25".to_owned()
+        }]
+    );
+}
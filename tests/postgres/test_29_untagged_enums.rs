@@ -0,0 +1,135 @@
+use serde::Deserialize;
+use serde_sqlx::DeserializeOptions;
+
+use crate::{conn, fetch_all, fetch_one};
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum Contact {
+    Email {
+        id: i32,
+        email: String,
+    },
+    Phone {
+        id: i32,
+        country_code: i32,
+        number: String,
+    },
+}
+
+#[tokio::test]
+async fn untagged_enum_chooses_by_present_columns() {
+    let email: Contact = fetch_one("SELECT 1 AS id, 'ann@example.com' AS email")
+        .await
+        .unwrap();
+    assert_eq!(
+        email,
+        Contact::Email {
+            id: 1,
+            email: "ann@example.com".to_owned()
+        }
+    );
+
+    let phone: Contact = fetch_one("SELECT 2 AS id, 44 AS country_code, '7700 900123' AS number")
+        .await
+        .unwrap();
+    assert_eq!(
+        phone,
+        Contact::Phone {
+            id: 2,
+            country_code: 44,
+            number: "7700 900123".to_owned(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn untagged_enum_skips_variants_with_null_columns() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(untagged)]
+    enum Owner {
+        User { user_id: i32 },
+        Team { team_id: i32 },
+    }
+
+    let owners: Vec<Owner> =
+        fetch_all("SELECT 1 AS user_id, NULL::INT AS team_id UNION ALL SELECT NULL, 2")
+            .await
+            .unwrap();
+
+    assert_eq!(
+        owners,
+        [Owner::User { user_id: 1 }, Owner::Team { team_id: 2 }]
+    );
+}
+
+#[tokio::test]
+async fn flattened_enum_from_the_remaining_columns() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Person {
+        name: String,
+        #[serde(flatten)]
+        contact: Contact,
+    }
+
+    let person: Person = fetch_one("SELECT 'ann' AS name, 3 AS id, 'ann@example.com' AS email")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        person,
+        Person {
+            name: "ann".to_owned(),
+            contact: Contact::Email {
+                id: 3,
+                email: "ann@example.com".to_owned()
+            },
+        }
+    );
+}
+
+#[tokio::test]
+async fn a_lone_column_is_its_value() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(untagged)]
+    enum Id {
+        Number(i64),
+        Text(String),
+    }
+
+    let id: Id = fetch_one("SELECT 7::INT8 AS id").await.unwrap();
+    assert_eq!(id, Id::Number(7));
+
+    let id: Id = fetch_one("SELECT 'abc' AS id").await.unwrap();
+    assert_eq!(id, Id::Text("abc".to_owned()));
+}
+
+#[tokio::test]
+async fn multi_column_rows_into_json_objects() {
+    let value: serde_json::Value = fetch_one("SELECT 1 AS id, 'ann' AS name").await.unwrap();
+
+    assert_eq!(value, serde_json::json!({ "id": 1, "name": "ann" }));
+}
+
+#[tokio::test]
+async fn positional_rows_are_sequences() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(untagged)]
+    enum Pair {
+        Numbers(i32, i32),
+        Named(String, i32),
+    }
+
+    let row = sqlx::query("SELECT 'ann' AS a, 2 AS b")
+        .fetch_one(&conn().await)
+        .await
+        .unwrap();
+    let options = DeserializeOptions::new().positional_rows(true);
+
+    let pair: Pair = serde_sqlx::from_row_ref_with::<sqlx::Postgres, _>(&row, &options).unwrap();
+    assert_eq!(pair, Pair::Named("ann".to_owned(), 2));
+
+    let value: serde_json::Value =
+        serde_sqlx::from_row_ref_with::<sqlx::Postgres, _>(&row, &options).unwrap();
+    assert_eq!(value, serde_json::json!(["ann", 2]));
+}
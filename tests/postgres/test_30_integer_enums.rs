@@ -0,0 +1,139 @@
+use serde::Deserialize;
+
+use crate::{fetch_all, fetch_one};
+
+/// Matched by variant index
+#[derive(Debug, Deserialize, PartialEq)]
+enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
+/// Matched by discriminant, through the variant names
+#[derive(Debug, Deserialize, PartialEq)]
+enum Status {
+    #[serde(rename = "10")]
+    Active,
+    #[serde(rename = "20")]
+    Suspended,
+    #[serde(rename = "-1")]
+    Deleted,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Ticket {
+    priority: Priority,
+    status: Status,
+}
+
+#[tokio::test]
+async fn smallint_by_index_as_a_whole_row() {
+    let priorities: Vec<Priority> =
+        fetch_all("SELECT 0::INT2 AS priority UNION ALL SELECT 2::INT2 UNION ALL SELECT 1::INT2")
+            .await
+            .unwrap();
+
+    assert_eq!(
+        priorities,
+        [Priority::Low, Priority::High, Priority::Medium]
+    );
+}
+
+#[tokio::test]
+async fn integers_as_struct_fields() {
+    let ticket: Ticket = fetch_one("SELECT 1::INT4 AS priority, -1::INT8 AS status")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        ticket,
+        Ticket {
+            priority: Priority::Medium,
+            status: Status::Deleted,
+        }
+    );
+}
+
+#[tokio::test]
+async fn optional_and_array_codes() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Row {
+        status: Option<Status>,
+        history: Vec<Status>,
+    }
+
+    let row: Row = fetch_one("SELECT NULL::INT2 AS status, ARRAY[10, 20]::INT2[] AS history")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        row,
+        Row {
+            status: None,
+            history: vec![Status::Active, Status::Suspended],
+        }
+    );
+}
+
+#[tokio::test]
+async fn unknown_codes_are_errors() {
+    let err = fetch_one::<Status>("SELECT 30::INT2 AS status")
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("unknown variant `30`, expected one of `10`, `20`, `-1`"),
+        "{err}"
+    );
+    assert!(err.to_string().contains("status"), "{err}");
+
+    let err = fetch_one::<Priority>("SELECT 3::INT2 AS priority")
+        .await
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("integer `3`"), "{message}");
+    assert!(message.contains("variant index 0 <= i < 3"), "{message}");
+
+    // Never an index, so not a variant by any name either
+    let err = fetch_one::<Priority>("SELECT -1::INT2 AS priority")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("unknown variant `-1`"), "{err}");
+}
+
+#[tokio::test]
+async fn catch_all_variant() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Code {
+        #[serde(rename = "1")]
+        Ok,
+        #[serde(other)]
+        Unknown,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Level {
+        Debug,
+        Info,
+        #[serde(other)]
+        Other,
+    }
+
+    let codes: Vec<Code> = fetch_all("SELECT 1 AS code UNION ALL SELECT 42")
+        .await
+        .unwrap();
+    assert_eq!(codes, [Code::Ok, Code::Unknown]);
+
+    let levels: Vec<Level> = fetch_all("SELECT 1 AS level UNION ALL SELECT 9")
+        .await
+        .unwrap();
+    assert_eq!(levels, [Level::Info, Level::Other]);
+
+    // Including codes that are no index at all
+    let levels: Vec<Level> =
+        fetch_all("SELECT -1::INT4 AS level UNION ALL SELECT 5000000000::INT8")
+            .await
+            .unwrap();
+    assert_eq!(levels, [Level::Other, Level::Other]);
+}
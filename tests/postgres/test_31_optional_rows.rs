@@ -0,0 +1,115 @@
+use serde::Deserialize;
+use serde_sqlx::DeserializeOptions;
+
+use crate::{conn, fetch_all, fetch_one};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Profile {
+    user_id: Option<i32>,
+    bio: Option<String>,
+}
+
+#[tokio::test]
+async fn none_only_when_every_column_is_null() {
+    let rows: Vec<Option<Profile>> = fetch_all(
+        "
+        SELECT NULL::INT AS user_id, NULL::TEXT AS bio
+        UNION ALL SELECT NULL, 'hello'
+        UNION ALL SELECT 1, NULL
+        ",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        [
+            None,
+            Some(Profile {
+                user_id: None,
+                bio: Some("hello".to_owned()),
+            }),
+            Some(Profile {
+                user_id: Some(1),
+                bio: None,
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn left_join_without_a_match() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct User {
+        id: i32,
+        name: String,
+    }
+
+    let user: Option<User> = fetch_one(
+        "
+        SELECT u.id, u.name
+        FROM (SELECT 1 AS k) k
+        LEFT JOIN (SELECT 2 AS k, 7 AS id, 'ann' AS name) u ON u.k = k.k
+        ",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(user, None);
+}
+
+#[tokio::test]
+async fn unmapped_columns_count_towards_some() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct User {
+        id: i32,
+        name: String,
+    }
+
+    // Every column is checked, not just those of the struct, so the joined `k` makes the row
+    // `Some` and the NULL `id` an error. A key column is the way to decide by `id` alone
+    let query = "
+        SELECT k.k, u.id, u.name
+        FROM (SELECT 1 AS k) k
+        LEFT JOIN (SELECT 2 AS k, 7 AS id, 'ann' AS name) u ON u.k = k.k
+    ";
+
+    let err = fetch_one::<Option<User>>(query).await.unwrap_err();
+    assert!(err.to_string().contains("unexpected NULL"), "{err}");
+
+    let row = sqlx::query(query).fetch_one(&conn().await).await.unwrap();
+    let options = DeserializeOptions::new().option_key_column("id");
+    let user: Option<User> = serde_sqlx::from_row_with::<sqlx::Postgres, _>(row, &options).unwrap();
+    assert_eq!(user, None);
+}
+
+#[tokio::test]
+async fn a_key_column_decides() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Address {
+        id: i32,
+        city: Option<String>,
+    }
+
+    let options = DeserializeOptions::new().option_key_column("id");
+    let fetch = |query: &'static str| {
+        let options = options.clone();
+        async move {
+            let row = sqlx::query(query).fetch_one(&conn().await).await.unwrap();
+            serde_sqlx::from_row_with::<sqlx::Postgres, Option<Address>>(row, &options)
+        }
+    };
+
+    // The city is known, but without an id there is no address
+    let address = fetch("SELECT NULL::INT AS id, 'Lisbon' AS city")
+        .await
+        .unwrap();
+    assert_eq!(address, None);
+
+    let address = fetch("SELECT 3 AS id, NULL::TEXT AS city").await.unwrap();
+    assert_eq!(address, Some(Address { id: 3, city: None }));
+
+    let err = fetch("SELECT 'Lisbon' AS city").await.unwrap_err();
+    assert!(err.to_string().contains("missing column"), "{err}");
+    assert_eq!(err.column(), Some("id"));
+}
@@ -0,0 +1,123 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Deserialize;
+
+use crate::fetch_all_with_schema;
+
+const SCHEMA: &str = "CREATE EXTENSION IF NOT EXISTS hstore;";
+
+#[tokio::test]
+async fn hstore_as_a_whole_row() {
+    let rows: Vec<HashMap<String, Option<String>>> =
+        fetch_all_with_schema(SCHEMA, "SELECT 'a => 1, b => NULL'::hstore AS attrs")
+            .await
+            .unwrap();
+
+    assert_eq!(
+        rows,
+        [HashMap::from([
+            ("a".to_owned(), Some("1".to_owned())),
+            ("b".to_owned(), None),
+        ])]
+    );
+}
+
+#[tokio::test]
+async fn hstore_fields() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Item {
+        id: i32,
+        attrs: BTreeMap<String, Option<String>>,
+        extra: Option<HashMap<String, Option<String>>>,
+    }
+
+    let rows: Vec<Item> = fetch_all_with_schema(
+        SCHEMA,
+        r#"SELECT 1 AS id, 'color => red, "size (cm)" => "10"'::hstore AS attrs, NULL::hstore AS extra"#,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        [Item {
+            id: 1,
+            attrs: BTreeMap::from([
+                ("color".to_owned(), Some("red".to_owned())),
+                ("size (cm)".to_owned(), Some("10".to_owned())),
+            ]),
+            extra: None,
+        }]
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Attributes {
+    color: String,
+    weight: Option<String>,
+}
+
+#[tokio::test]
+async fn hstore_into_a_struct_by_key() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Item {
+        attrs: Attributes,
+    }
+
+    let items: Vec<Item> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT 'color => red, weight => NULL, unused => x'::hstore AS attrs",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        items,
+        [Item {
+            attrs: Attributes {
+                color: "red".to_owned(),
+                weight: None,
+            }
+        }]
+    );
+
+    // A row of just the hstore is the struct itself
+    let attributes: Vec<Attributes> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT 'color => blue, weight => 3kg'::hstore AS attrs",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        attributes,
+        [Attributes {
+            color: "blue".to_owned(),
+            weight: Some("3kg".to_owned()),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn hstore_arrays() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Row {
+        versions: Vec<Option<HashMap<String, Option<String>>>>,
+    }
+
+    let rows: Vec<Row> = fetch_all_with_schema(
+        SCHEMA,
+        "SELECT ARRAY['v => 1'::hstore, NULL, ''::hstore] AS versions",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        [Row {
+            versions: vec![
+                Some(HashMap::from([("v".to_owned(), Some("1".to_owned()))])),
+                None,
+                Some(HashMap::new()),
+            ],
+        }]
+    );
+}
@@ -0,0 +1,162 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use serde::Deserialize;
+
+use crate::{fetch_all, fetch_one};
+
+#[tokio::test]
+async fn inet_hosts_into_ip_addr() {
+    let addresses: Vec<IpAddr> =
+        fetch_all("SELECT '192.168.0.1'::INET AS address UNION ALL SELECT '::1'::INET")
+            .await
+            .unwrap();
+
+    assert_eq!(
+        addresses,
+        [
+            IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1)),
+            IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn networks_as_postgres_text() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Rule {
+        source: String,
+        network: String,
+        v6: String,
+    }
+
+    let rule: Rule = fetch_one(
+        "SELECT '10.1.2.3/8'::INET AS source, '10.0.0.0/8'::CIDR AS network,
+            '2001:db8::/32'::CIDR AS v6",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rule,
+        Rule {
+            source: "10.1.2.3/8".to_owned(),
+            network: "10.0.0.0/8".to_owned(),
+            v6: "2001:db8::/32".to_owned(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn networks_as_address_and_prefix() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Network {
+        address: IpAddr,
+        prefix: u8,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Rule {
+        network: Network,
+        pair: (IpAddr, u8),
+    }
+
+    let rule: Rule =
+        fetch_one("SELECT '10.0.0.0/8'::CIDR AS network, '192.168.1.7/24'::INET AS pair")
+            .await
+            .unwrap();
+
+    assert_eq!(
+        rule,
+        Rule {
+            network: Network {
+                address: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)),
+                prefix: 8,
+            },
+            pair: (IpAddr::V4(Ipv4Addr::new(192, 168, 1, 7)), 24),
+        }
+    );
+
+    // A row of just the network is the pair itself
+    let pair: (IpAddr, u8) = fetch_one("SELECT '::1/128'::INET").await.unwrap();
+    assert_eq!(pair, (IpAddr::V6(Ipv6Addr::LOCALHOST), 128));
+}
+
+#[tokio::test]
+async fn mac_addresses_as_text_and_bytes() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Device {
+        mac: String,
+        mac_bytes: [u8; 6],
+        mac8: String,
+        mac8_bytes: Vec<u8>,
+    }
+
+    let device: Device = fetch_one(
+        "SELECT '08:00:2B:01:02:03'::MACADDR AS mac, '08-00-2b-01-02-03'::MACADDR AS mac_bytes,
+            '08:00:2b:01:02:03:04:05'::MACADDR8 AS mac8,
+            '08002b0102030405'::MACADDR8 AS mac8_bytes",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        device,
+        Device {
+            mac: "08:00:2b:01:02:03".to_owned(),
+            mac_bytes: [0x08, 0x00, 0x2b, 0x01, 0x02, 0x03],
+            mac8: "08:00:2b:01:02:03:04:05".to_owned(),
+            mac8_bytes: vec![0x08, 0x00, 0x2b, 0x01, 0x02, 0x03, 0x04, 0x05],
+        }
+    );
+
+    let mac: [u8; 6] = fetch_one("SELECT 'ff:ff:ff:ff:ff:ff'::MACADDR")
+        .await
+        .unwrap();
+    assert_eq!(mac, [0xff; 6]);
+}
+
+#[tokio::test]
+async fn arrays_of_network_types() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Audit {
+        hosts: Vec<IpAddr>,
+        networks: Vec<Option<String>>,
+        macs: Vec<String>,
+        macs8: Vec<[u8; 8]>,
+    }
+
+    let audit: Audit = fetch_one(
+        "SELECT ARRAY['1.1.1.1', '::2']::INET[] AS hosts,
+            ARRAY['10.0.0.0/8', NULL]::CIDR[] AS networks,
+            ARRAY['aa:bb:cc:dd:ee:ff']::MACADDR[] AS macs,
+            ARRAY['01:02:03:04:05:06:07:08']::MACADDR8[] AS macs8",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        audit,
+        Audit {
+            hosts: vec![
+                IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)),
+                "::2".parse().unwrap()
+            ],
+            networks: vec![Some("10.0.0.0/8".to_owned()), None],
+            macs: vec!["aa:bb:cc:dd:ee:ff".to_owned()],
+            macs8: vec![[1, 2, 3, 4, 5, 6, 7, 8]],
+        }
+    );
+}
+
+#[tokio::test]
+async fn network_types_in_records() {
+    let rows: Vec<((String, String),)> =
+        fetch_all("SELECT ROW('10.0.0.1'::INET, '01:02:03:04:05:06'::MACADDR)")
+            .await
+            .unwrap();
+
+    assert_eq!(
+        rows,
+        [(("10.0.0.1".to_owned(), "01:02:03:04:05:06".to_owned()),)]
+    );
+}
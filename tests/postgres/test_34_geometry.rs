@@ -0,0 +1,150 @@
+use serde::Deserialize;
+use serde_sqlx::types::geo::{Circle, Line, LineSegment, Path, Point, Polygon, Rect};
+
+use crate::{fetch_all, fetch_one};
+
+#[tokio::test]
+async fn every_geometric_type_into_the_geo_types() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Shapes {
+        point: Point,
+        line: Line,
+        lseg: LineSegment,
+        rect: Rect,
+        open_path: Path,
+        closed_path: Path,
+        polygon: Polygon,
+        circle: Circle,
+    }
+
+    let shapes: Shapes = fetch_one(
+        "SELECT '(1.5,2)'::POINT AS point, '{1,-1,0}'::LINE AS line,
+            '[(0,0),(3,4)]'::LSEG AS lseg, '(0,0),(10,5)'::BOX AS rect,
+            '[(0,0),(1,1)]'::PATH AS open_path, '((0,0),(1,1),(1,0))'::PATH AS closed_path,
+            '((0,0),(4,0),(4,3))'::POLYGON AS polygon, '<(1,1),2.5>'::CIRCLE AS circle",
+    )
+    .await
+    .unwrap();
+
+    let p = |x, y| Point { x, y };
+    assert_eq!(
+        shapes,
+        Shapes {
+            point: p(1.5, 2.0),
+            line: Line {
+                a: 1.0,
+                b: -1.0,
+                c: 0.0
+            },
+            lseg: LineSegment {
+                start: p(0.0, 0.0),
+                end: p(3.0, 4.0),
+            },
+            rect: Rect {
+                high: p(10.0, 5.0),
+                low: p(0.0, 0.0),
+            },
+            open_path: Path {
+                closed: false,
+                points: vec![p(0.0, 0.0), p(1.0, 1.0)],
+            },
+            closed_path: Path {
+                closed: true,
+                points: vec![p(0.0, 0.0), p(1.0, 1.0), p(1.0, 0.0)],
+            },
+            polygon: Polygon {
+                points: vec![p(0.0, 0.0), p(4.0, 0.0), p(4.0, 3.0)],
+            },
+            circle: Circle {
+                center: p(1.0, 1.0),
+                radius: 2.5,
+            },
+        }
+    );
+}
+
+#[tokio::test]
+async fn geometric_types_as_tuples_and_sequences() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Room {
+        door: (f64, f64),
+        bounds: ((f64, f64), (f64, f64)),
+        outline: Vec<(f64, f64)>,
+        route: Vec<[f64; 2]>,
+        lamp: ((f64, f64), f64),
+    }
+
+    let room: Room = fetch_one(
+        "SELECT '(1,0)'::POINT AS door, '(4,3),(0,0)'::BOX AS bounds,
+            '((0,0),(4,0),(4,3),(0,3))'::POLYGON AS outline, '[(0,0),(2,2)]'::PATH AS route,
+            '<(2,1.5),0.5>'::CIRCLE AS lamp",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        room,
+        Room {
+            door: (1.0, 0.0),
+            bounds: ((4.0, 3.0), (0.0, 0.0)),
+            outline: vec![(0.0, 0.0), (4.0, 0.0), (4.0, 3.0), (0.0, 3.0)],
+            route: vec![[0.0, 0.0], [2.0, 2.0]],
+            lamp: ((2.0, 1.5), 0.5),
+        }
+    );
+}
+
+#[tokio::test]
+async fn whole_rows_and_arrays() {
+    let points: Vec<Point> = fetch_all("SELECT '(1,2)'::POINT UNION ALL SELECT '(3,4)'::POINT")
+        .await
+        .unwrap();
+    assert_eq!(points, [Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 4.0 }]);
+
+    let pair: (f64, f64) = fetch_one("SELECT '(5,6)'::POINT").await.unwrap();
+    assert_eq!(pair, (5.0, 6.0));
+
+    let outline: Vec<(f64, f64)> = fetch_one("SELECT '((0,0),(1,0),(0,1))'::POLYGON")
+        .await
+        .unwrap();
+    assert_eq!(outline, [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]);
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Plan {
+        rooms: Vec<Rect>,
+        pins: Vec<Option<Point>>,
+    }
+
+    let plan: Plan = fetch_one(
+        "SELECT ARRAY['(2,2),(0,0)'::BOX, '(5,5),(3,3)'::BOX] AS rooms,
+            ARRAY['(1,1)'::POINT, NULL] AS pins",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        plan,
+        Plan {
+            rooms: vec![
+                Rect {
+                    high: Point { x: 2.0, y: 2.0 },
+                    low: Point { x: 0.0, y: 0.0 },
+                },
+                Rect {
+                    high: Point { x: 5.0, y: 5.0 },
+                    low: Point { x: 3.0, y: 3.0 },
+                },
+            ],
+            pins: vec![Some(Point { x: 1.0, y: 1.0 }), None],
+        }
+    );
+}
+
+#[tokio::test]
+async fn geometric_types_into_json() {
+    let value: serde_json::Value = fetch_one("SELECT '<(1,2),3>'::CIRCLE").await.unwrap();
+
+    assert_eq!(
+        value,
+        serde_json::json!({ "center": { "x": 1.0, "y": 2.0 }, "radius": 3.0 })
+    );
+}
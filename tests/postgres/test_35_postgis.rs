@@ -0,0 +1,190 @@
+//! PostGIS isn't needed to test EWKB parsing: a `geometry` type made with `bytea_types_schema`
+//! is sent the same way, as the raw EWKB bytes
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{bytea_types_schema, fetch_all_with_schema};
+
+fn schema() -> String {
+    bytea_types_schema(&["geometry"])
+}
+
+async fn geojson(ewkb: &str) -> serde_json::Value {
+    let query = format!("SELECT '\\x{ewkb}'::pg_temp.geometry AS geom");
+    let mut rows: Vec<serde_json::Value> = fetch_all_with_schema(&schema(), &query).await.unwrap();
+    rows.remove(0)
+}
+
+#[tokio::test]
+async fn points_with_and_without_srid() {
+    assert_eq!(
+        geojson("0101000020e6100000000000000000f03f0000000000000040").await,
+        json!({ "type": "Point", "coordinates": [1.0, 2.0], "srid": 4326 })
+    );
+
+    // Big endian
+    assert_eq!(
+        geojson("000000000140080000000000004010000000000000").await,
+        json!({ "type": "Point", "coordinates": [3.0, 4.0] })
+    );
+
+    // With a Z coordinate
+    assert_eq!(
+        geojson("0101000080000000000000f03f00000000000000400000000000000840").await,
+        json!({ "type": "Point", "coordinates": [1.0, 2.0, 3.0] })
+    );
+
+    // `POINT EMPTY`
+    assert_eq!(
+        geojson("0101000000000000000000f87f000000000000f87f").await,
+        json!({ "type": "Point", "coordinates": [] })
+    );
+}
+
+#[tokio::test]
+async fn lines_polygons_and_collections() {
+    assert_eq!(
+        geojson("0102000020110f00000200000000000000000000000000000000000000000000000000f03f000000000000f03f").await,
+        json!({ "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]], "srid": 3857 })
+    );
+
+    assert_eq!(
+        geojson("0103000000010000000400000000000000000000000000000000000000000000000000f03f00000000000000000000000000000000000000000000f03f00000000000000000000000000000000").await,
+        json!({
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [0.0, 0.0]]],
+        })
+    );
+
+    assert_eq!(
+        geojson("0104000020e6100000020000000101000000000000000000f03f0000000000000040010100000000000000000008400000000000001040").await,
+        json!({ "type": "MultiPoint", "coordinates": [[1.0, 2.0], [3.0, 4.0]], "srid": 4326 })
+    );
+
+    assert_eq!(
+        geojson("0107000020e6100000020000000101000000000000000000f03f000000000000004001020000000200000000000000000000000000000000000000000000000000f03f000000000000f03f").await,
+        json!({
+            "type": "GeometryCollection",
+            "geometries": [
+                { "type": "Point", "coordinates": [1.0, 2.0] },
+                { "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]] },
+            ],
+            "srid": 4326,
+        })
+    );
+}
+
+#[tokio::test]
+async fn empty_rings_and_members() {
+    // Three empty rings
+    assert_eq!(
+        geojson("010300000003000000000000000000000000000000").await,
+        json!({ "type": "Polygon", "coordinates": [[], [], []] })
+    );
+
+    // A polygon with one empty ring, an empty line string and an empty collection
+    assert_eq!(
+        geojson("01070000000300000001030000000100000000000000010200000000000000010700000000000000")
+            .await,
+        json!({
+            "type": "GeometryCollection",
+            "geometries": [
+                { "type": "Polygon", "coordinates": [[]] },
+                { "type": "LineString", "coordinates": [] },
+                { "type": "GeometryCollection", "geometries": [] },
+            ],
+        })
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+enum Geometry {
+    Point { coordinates: [f64; 2] },
+    LineString { coordinates: Vec<[f64; 2]> },
+}
+
+#[tokio::test]
+async fn geometries_into_own_types() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Place {
+        id: i32,
+        location: Geometry,
+        route: Option<Geometry>,
+        area: Option<Geometry>,
+    }
+
+    let places: Vec<Place> = fetch_all_with_schema(
+        &schema(),
+        "SELECT 1 AS id,
+            '\\x0101000020e6100000000000000000f03f0000000000000040'::pg_temp.geometry AS location,
+            '\\x0102000020110f00000200000000000000000000000000000000000000000000000000f03f000000000000f03f'::pg_temp.geometry AS route,
+            NULL::pg_temp.geometry AS area",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        places,
+        [Place {
+            id: 1,
+            location: Geometry::Point {
+                coordinates: [1.0, 2.0]
+            },
+            route: Some(Geometry::LineString {
+                coordinates: vec![[0.0, 0.0], [1.0, 1.0]]
+            }),
+            area: None,
+        }]
+    );
+
+    // A row of just the geometry, and arrays of them
+    let points: Vec<Geometry> = fetch_all_with_schema(
+        &schema(),
+        "SELECT '\\x0101000020e6100000000000000000f03f0000000000000040'::pg_temp.geometry",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        points,
+        [Geometry::Point {
+            coordinates: [1.0, 2.0]
+        }]
+    );
+
+    let arrays: Vec<(Vec<Geometry>,)> = fetch_all_with_schema(
+        &schema(),
+        "SELECT ARRAY['\\x0101000020e6100000000000000000f03f0000000000000040'::pg_temp.geometry]",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        arrays,
+        [(vec![Geometry::Point {
+            coordinates: [1.0, 2.0]
+        }],)]
+    );
+}
+
+#[tokio::test]
+async fn invalid_ewkb_is_an_error() {
+    let err = fetch_all_with_schema::<serde_json::Value>(
+        &schema(),
+        "SELECT '\\x0101000000'::pg_temp.geometry AS geom",
+    )
+    .await
+    .unwrap_err();
+
+    assert!(err.to_string().contains("unexpected end of EWKB"), "{err}");
+
+    // More positions than there are bytes for
+    let err = fetch_all_with_schema::<serde_json::Value>(
+        &schema(),
+        "SELECT '\\x0102000000ffffff7f0000000000000000'::pg_temp.geometry AS geom",
+    )
+    .await
+    .unwrap_err();
+
+    assert!(err.to_string().contains("invalid EWKB length"), "{err}");
+}
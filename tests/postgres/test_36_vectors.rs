@@ -0,0 +1,154 @@
+//! pgvector isn't needed to test its binary formats: types named like its own, made with
+//! `bytea_types_schema`, are sent the same way, as the bytes they are given
+
+use serde::Deserialize;
+use serde_sqlx::types::{SparseVector, Vector};
+
+use crate::{bytea_types_schema, fetch_all_with_schema};
+
+fn schema() -> String {
+    bytea_types_schema(&["vector", "halfvec", "sparsevec"])
+}
+
+/// `[1, 2.5, -3]`
+const VECTOR: &str = "'\\x000300003f80000040200000c0400000'::pg_temp.vector";
+/// `[1, 0.5, -2]`
+const HALFVEC: &str = "'\\x000300003c003800c000'::pg_temp.halfvec";
+/// `{1:1.5,3:2}/5`
+const SPARSEVEC: &str =
+    "'\\x00000005000000020000000000000000000000023fc0000040000000'::pg_temp.sparsevec";
+
+#[tokio::test]
+async fn vectors_into_fields() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Document {
+        id: i32,
+        embedding: Vec<f32>,
+        small: [f32; 3],
+        sparse: SparseVector,
+        missing: Option<Vec<f32>>,
+    }
+
+    let documents: Vec<Document> = fetch_all_with_schema(
+        &schema(),
+        &format!(
+            "SELECT 1 AS id, {VECTOR} AS embedding, {HALFVEC} AS small, {SPARSEVEC} AS sparse,
+                NULL::pg_temp.vector AS missing"
+        ),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        documents,
+        [Document {
+            id: 1,
+            embedding: vec![1.0, 2.5, -3.0],
+            small: [1.0, 0.5, -2.0],
+            sparse: SparseVector {
+                indices: vec![0, 2],
+                values: vec![1.5, 2.0],
+                dim: 5,
+            },
+            missing: None,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn vectors_as_rows() {
+    let rows: Vec<Vec<f32>> = fetch_all_with_schema(&schema(), &format!("SELECT {VECTOR}"))
+        .await
+        .unwrap();
+    assert_eq!(rows, [vec![1.0, 2.5, -3.0]]);
+
+    let rows: Vec<[f32; 3]> = fetch_all_with_schema(&schema(), &format!("SELECT {HALFVEC}"))
+        .await
+        .unwrap();
+    assert_eq!(rows, [[1.0, 0.5, -2.0]]);
+
+    let rows: Vec<SparseVector> = fetch_all_with_schema(&schema(), &format!("SELECT {SPARSEVEC}"))
+        .await
+        .unwrap();
+    assert_eq!(rows[0].indices, [0, 2]);
+
+    // A sparse vector fills in its zeros as a dense one
+    let rows: Vec<Vec<f32>> = fetch_all_with_schema(&schema(), &format!("SELECT {SPARSEVEC}"))
+        .await
+        .unwrap();
+    assert_eq!(rows, [vec![1.5, 0.0, 2.0, 0.0, 0.0]]);
+
+    let rows: Vec<serde_json::Value> = fetch_all_with_schema(
+        &schema(),
+        &format!("SELECT {SPARSEVEC} AS sparse, ARRAY[{VECTOR}] AS vectors"),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        rows,
+        [serde_json::json!({
+            "sparse": { "indices": [0, 2], "values": [1.5, 2.0], "dim": 5 },
+            "vectors": [[1.0, 2.5, -3.0]],
+        })]
+    );
+}
+
+#[tokio::test]
+async fn vector_type_from_vectors_and_text() {
+    let rows: Vec<(Vector, Vector, Vector)> = fetch_all_with_schema(
+        &schema(),
+        &format!("SELECT {VECTOR}, {HALFVEC}, '[1, 2.5, -3]'::TEXT"),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        [(
+            Vector(vec![1.0, 2.5, -3.0]),
+            Vector(vec![1.0, 0.5, -2.0]),
+            Vector(vec![1.0, 2.5, -3.0]),
+        )]
+    );
+}
+
+#[tokio::test]
+async fn wrong_dimensions_are_an_error() {
+    let err = fetch_all_with_schema::<[f32; 4]>(&schema(), &format!("SELECT {VECTOR}"))
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("invalid length 3"), "{err}");
+}
+
+#[tokio::test]
+async fn corrupt_sparse_vectors_are_an_error() {
+    let fetch = |hex: &'static str| async move {
+        let query = format!("SELECT '\\x{hex}'::pg_temp.sparsevec");
+        fetch_all_with_schema::<SparseVector>(&schema(), &query).await
+    };
+
+    // More dimensions than pgvector allows
+    let err = fetch("7fffffff0000000000000000").await.unwrap_err();
+    assert!(err.to_string().contains("dimensions"), "{err}");
+
+    // An index past the dimensions
+    let err = fetch("0000000200000001000000000000000540000000")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("out of bounds"), "{err}");
+
+    // A sparse vector too large to fill in as a dense one
+    let rows = fetch("000186a00000000100000000000000073f800000")
+        .await
+        .unwrap();
+    assert_eq!(rows[0].dim, 100_000);
+
+    let err = fetch_all_with_schema::<Vec<f32>>(
+        &schema(),
+        "SELECT '\\x000186a00000000100000000000000073f800000'::pg_temp.sparsevec",
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("too large"), "{err}");
+}
@@ -0,0 +1,157 @@
+//! `sqlx::raw_sql` uses the simple query protocol, which sends every value as text
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_sqlx::types::Interval;
+
+use crate::conn;
+
+async fn fetch_raw<T: for<'de> Deserialize<'de>>(query: &str) -> T {
+    let row = sqlx::raw_sql(query).fetch_one(&conn().await).await.unwrap();
+    serde_sqlx::from_row::<sqlx::Postgres, _>(row).unwrap()
+}
+
+#[tokio::test]
+async fn text_values_into_struct() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Row {
+        small: i16,
+        int: i32,
+        big: i64,
+        flag: bool,
+        real: f32,
+        double: f64,
+        exact: f64,
+        name: String,
+        data: serde_json::Value,
+    }
+
+    let row: Row = fetch_raw(
+        "SELECT 1::INT2 AS small, 2::INT4 AS int, 3::INT8 AS big, true AS flag,
+            1.5::FLOAT4 AS real, 2.5::FLOAT8 AS double, 2.25::NUMERIC AS exact,
+            'Alice' AS name, '{\"a\": 1}'::JSONB AS data",
+    )
+    .await;
+
+    assert_eq!(
+        row,
+        Row {
+            small: 1,
+            int: 2,
+            big: 3,
+            flag: true,
+            real: 1.5,
+            double: 2.5,
+            exact: 2.25,
+            name: "Alice".to_owned(),
+            data: serde_json::json!({ "a": 1 }),
+        }
+    );
+}
+
+#[tokio::test]
+async fn text_values_as_primitives() {
+    assert_eq!(
+        fetch_raw::<(i32, bool)>("SELECT 1, false").await,
+        (1, false)
+    );
+    assert_eq!(
+        fetch_raw::<f64>("SELECT '-Infinity'::FLOAT8").await,
+        f64::NEG_INFINITY
+    );
+    assert_eq!(
+        fetch_raw::<String>("SELECT 12345678901234567.89::NUMERIC").await,
+        "12345678901234567.89"
+    );
+    assert_eq!(
+        fetch_raw::<chrono::NaiveDateTime>("SELECT '2024-01-02 03:04:05.5'::TIMESTAMP").await,
+        chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_milli_opt(3, 4, 5, 500)
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn text_array_as_whole_row() {
+    assert_eq!(
+        fetch_raw::<Vec<String>>("SELECT ARRAY['a', 'b']").await,
+        vec!["a".to_owned(), "b".to_owned()]
+    );
+}
+
+#[tokio::test]
+async fn text_arrays_into_struct() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Row {
+        ints: Vec<i32>,
+        floats: Vec<f64>,
+        flags: Vec<bool>,
+        // Quoted as they contain the delimiter, quotes, backslashes, or would be NULL otherwise
+        names: Vec<Option<String>>,
+        matrix: Vec<Vec<i64>>,
+        empty: Vec<String>,
+    }
+
+    let row: Row = fetch_raw(
+        r#"SELECT ARRAY[1, -2]::INT4[] AS ints,
+            ARRAY[1.5, '-Infinity']::FLOAT8[] AS floats,
+            ARRAY[true, false] AS flags,
+            ARRAY['a,b', 'say "hi"', 'back\slash', NULL, 'NULL', ''] AS names,
+            ARRAY[[1, 2], [3, 4]]::INT8[] AS matrix,
+            ARRAY[]::TEXT[] AS empty"#,
+    )
+    .await;
+
+    assert_eq!(
+        row,
+        Row {
+            ints: vec![1, -2],
+            floats: vec![1.5, f64::NEG_INFINITY],
+            flags: vec![true, false],
+            names: vec![
+                Some("a,b".to_owned()),
+                Some("say \"hi\"".to_owned()),
+                Some("back\\slash".to_owned()),
+                None,
+                Some("NULL".to_owned()),
+                Some(String::new()),
+            ],
+            matrix: vec![vec![1, 2], vec![3, 4]],
+            empty: vec![],
+        }
+    );
+}
+
+#[tokio::test]
+async fn text_array_keeps_its_lower_bound_as_map() {
+    #[derive(Debug, Deserialize)]
+    struct Row {
+        values: BTreeMap<i32, i32>,
+    }
+
+    let row: Row = fetch_raw("SELECT '[0:1]={7,8}'::INT4[] AS values, 1 AS other").await;
+
+    assert_eq!(row.values, BTreeMap::from([(0, 7), (1, 8)]));
+}
+
+#[tokio::test]
+async fn compound_text_values_are_only_strings() {
+    assert_eq!(
+        fetch_raw::<String>("SELECT '1 day'::INTERVAL").await,
+        "1 day"
+    );
+
+    let row = sqlx::raw_sql("SELECT '1 day'::INTERVAL")
+        .fetch_one(&conn().await)
+        .await
+        .unwrap();
+    let err = serde_sqlx::from_row::<sqlx::Postgres, Interval>(row).unwrap_err();
+
+    assert!(
+        matches!(err, serde_sqlx::Error::TypeMismatch { .. }),
+        "{err:?}"
+    );
+    assert!(err.to_string().contains("found string \"1 day\""), "{err}");
+}
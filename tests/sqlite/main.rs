@@ -0,0 +1,82 @@
+use sqlx::{Connection, SqliteConnection};
+
+mod test_01_strings;
+mod test_02_bool;
+mod test_03_integer;
+mod test_04_float;
+mod test_05_newtype_over_primitive;
+mod test_06_structs_with_prim_fields;
+mod test_07_tuples_and_tuple_structs;
+mod test_08_same_type_columns_into_hashmap;
+mod test_09_record_with_flatten;
+mod test_10_enums;
+mod test_11_declared_types;
+mod test_12_blob;
+mod test_13_errors;
+mod test_14_query_as;
+mod test_15_borrowed;
+mod test_16_column_names;
+
+#[allow(unused)]
+pub async fn fetch_one<T: for<'de> serde::Deserialize<'de>>(query: &str) -> anyhow::Result<T> {
+    fetch_one_with_schema("", query).await
+}
+
+#[allow(unused)]
+pub async fn fetch_all<T: for<'de> serde::Deserialize<'de>>(query: &str) -> anyhow::Result<Vec<T>> {
+    fetch_all_with_schema("", query).await
+}
+
+#[allow(unused)]
+pub async fn fetch_optional<T: for<'de> serde::Deserialize<'de>>(
+    query: &str,
+) -> anyhow::Result<Option<T>> {
+    let mut conn = conn("").await;
+
+    let row = sqlx::query(query).fetch_optional(&mut conn).await.unwrap();
+
+    row.map(|row| serde_sqlx::from_row::<sqlx::Sqlite, _>(row))
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// Runs `schema` against a fresh in-memory database before fetching a single row with `query`
+#[allow(unused)]
+pub async fn fetch_one_with_schema<T: for<'de> serde::Deserialize<'de>>(
+    schema: &str,
+    query: &str,
+) -> anyhow::Result<T> {
+    let mut conn = conn(schema).await;
+
+    let row = sqlx::query(query).fetch_one(&mut conn).await.unwrap();
+
+    serde_sqlx::from_row::<sqlx::Sqlite, _>(row).map_err(Into::into)
+}
+
+/// Runs `schema` against a fresh in-memory database before fetching all rows with `query`
+#[allow(unused)]
+pub async fn fetch_all_with_schema<T: for<'de> serde::Deserialize<'de>>(
+    schema: &str,
+    query: &str,
+) -> anyhow::Result<Vec<T>> {
+    let mut conn = conn(schema).await;
+
+    let row = sqlx::query(query).fetch_all(&mut conn).await.unwrap();
+    let result: Result<Vec<_>, _> = row
+        .into_iter()
+        .map(serde_sqlx::from_row::<sqlx::Sqlite, _>)
+        .collect();
+
+    result.map_err(Into::into)
+}
+
+/// Every connection gets its own in-memory database, so tests can't interfere with each other
+pub async fn conn(schema: &str) -> SqliteConnection {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+
+    if !schema.is_empty() {
+        sqlx::raw_sql(schema).execute(&mut conn).await.unwrap();
+    }
+
+    conn
+}
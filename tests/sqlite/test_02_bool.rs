@@ -0,0 +1,63 @@
+use serde::Deserialize;
+
+use crate::{fetch_all, fetch_all_with_schema, fetch_one};
+
+#[tokio::test]
+async fn unannotated_as_bool() {
+    let rows: Vec<bool> = fetch_all("SELECT true UNION ALL SELECT false")
+        .await
+        .unwrap();
+
+    assert_eq!(rows, [true, false]);
+}
+
+#[tokio::test]
+async fn unannotated_as_bool_opt() {
+    let rows =
+        fetch_all::<Option<bool>>("SELECT true UNION ALL SELECT false UNION ALL SELECT NULL")
+            .await
+            .unwrap();
+
+    assert_eq!(rows, [Some(true), Some(false), None])
+}
+
+#[tokio::test]
+async fn test_integer_as_bool_true() {
+    let val: bool = fetch_one("SELECT 1").await.unwrap();
+    assert!(val);
+}
+
+#[tokio::test]
+async fn test_integer_as_bool_false() {
+    let val: bool = fetch_one("SELECT 0").await.unwrap();
+    assert!(!val);
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+struct InnerBool {
+    inner: bool,
+}
+
+#[tokio::test]
+async fn test_integer_as_bool_inner() {
+    let val: Vec<InnerBool> = fetch_all("SELECT 1 AS inner UNION ALL SELECT 0")
+        .await
+        .unwrap();
+    assert_eq!(val, [InnerBool { inner: true }, InnerBool { inner: false }]);
+}
+
+#[tokio::test]
+async fn declared_boolean_column_into_json_value() {
+    // Without a `bool` target only the declared type tells us this is not an integer
+    let rows: Vec<serde_json::Value> = fetch_all_with_schema(
+        "
+            CREATE TABLE flags (flag BOOLEAN);
+            INSERT INTO flags VALUES (1), (0);
+        ",
+        "SELECT flag FROM flags",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(rows, [serde_json::json!(true), serde_json::json!(false)]);
+}
@@ -0,0 +1,45 @@
+use crate::{fetch_one, fetch_optional};
+use paste::paste;
+
+macro_rules! create_int_tests {
+    ($ty: ident) => {
+        paste! {
+            #[tokio::test]
+            async fn [<unannotated_as_ $ty>]() {
+                let row: $ty = fetch_one("SELECT 42 AS value").await.unwrap();
+                assert_eq!(row, 42);
+            }
+
+            #[tokio::test]
+            async fn [<cast_as_ $ty>]() {
+                let row: $ty = fetch_one("SELECT CAST('42' AS INTEGER) AS value")
+                    .await
+                    .unwrap();
+                assert_eq!(row, 42);
+            }
+
+            #[tokio::test]
+            async fn [<unannotated_as_ $ty _opt>]() {
+                let row: Option<$ty> = fetch_optional("SELECT 42 AS value UNION ALL SELECT NULL")
+                    .await
+                    .unwrap();
+                assert_eq!(row, Some(42));
+            }
+        }
+    };
+}
+
+create_int_tests!(i8);
+create_int_tests!(u8);
+create_int_tests!(i16);
+create_int_tests!(u16);
+create_int_tests!(i32);
+create_int_tests!(u32);
+create_int_tests!(i64);
+create_int_tests!(u64);
+
+#[tokio::test]
+async fn negative_into_unsigned_fails() {
+    let res = fetch_one::<u32>("SELECT -1 AS value").await;
+    assert!(res.is_err());
+}
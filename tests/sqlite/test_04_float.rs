@@ -0,0 +1,29 @@
+use crate::{fetch_all, fetch_one};
+
+#[tokio::test]
+async fn unannotated_as_f32() {
+    let row: f32 = fetch_one("SELECT -4.2 AS value").await.unwrap();
+    assert_eq!(row, -4.2);
+}
+
+#[tokio::test]
+async fn unannotated_as_f64() {
+    let row: f64 = fetch_one("SELECT -4.2 AS value").await.unwrap();
+    assert_eq!(row, -4.2);
+}
+
+#[tokio::test]
+async fn cast_real_as_f64() {
+    let row: f64 = fetch_one("SELECT CAST('-4.2' AS REAL) AS value")
+        .await
+        .unwrap();
+    assert_eq!(row, -4.2);
+}
+
+#[tokio::test]
+async fn unannotated_as_f64_opt() {
+    let row: Vec<Option<f64>> = fetch_all("SELECT -4.2 AS value UNION ALL SELECT NULL")
+        .await
+        .unwrap();
+    assert_eq!(row, vec![Some(-4.2), None]);
+}
@@ -0,0 +1,119 @@
+use crate::{fetch_all, fetch_all_with_schema};
+
+#[tokio::test]
+async fn two_prim_field_struct() {
+    #[derive(Debug, ::serde::Deserialize, PartialEq, Eq)]
+    struct TwoFieldStruct {
+        first_field: bool,
+        second_field: i32,
+    }
+
+    let rows: Vec<TwoFieldStruct> =
+        fetch_all("SELECT true AS first_field, 42 AS second_field UNION ALL SELECT false, 0")
+            .await
+            .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            TwoFieldStruct {
+                first_field: true,
+                second_field: 42
+            },
+            TwoFieldStruct {
+                first_field: false,
+                second_field: 0
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn struct_from_table() {
+    #[derive(Debug, ::serde::Deserialize, PartialEq)]
+    struct User {
+        id: i64,
+        name: String,
+        score: f64,
+        active: bool,
+        nickname: Option<String>,
+    }
+
+    let rows: Vec<User> = fetch_all_with_schema(
+        "
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                score REAL NOT NULL,
+                active BOOLEAN NOT NULL,
+                nickname TEXT
+            );
+            INSERT INTO users VALUES (1, 'alice', 9.5, 1, 'al'), (2, 'bob', 7.25, 0, NULL);
+        ",
+        "SELECT * FROM users ORDER BY id",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            User {
+                id: 1,
+                name: "alice".to_owned(),
+                score: 9.5,
+                active: true,
+                nickname: Some("al".to_owned()),
+            },
+            User {
+                id: 2,
+                name: "bob".to_owned(),
+                score: 7.25,
+                active: false,
+                nickname: None,
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn two_indirect_prim_fields_struct() {
+    #[derive(Debug, ::serde::Deserialize, PartialEq, Eq)]
+    struct Left {
+        first_field: bool,
+    }
+    #[derive(Debug, ::serde::Deserialize, PartialEq, Eq)]
+    struct Right {
+        second_field: i32,
+    }
+
+    #[derive(Debug, ::serde::Deserialize, PartialEq, Eq)]
+    struct OneFieldStruct {
+        #[serde(flatten)]
+        left: Left,
+        #[serde(flatten)]
+        right: Right,
+    }
+
+    let rows: Vec<OneFieldStruct> = fetch_all_with_schema(
+        "
+            CREATE TABLE t (first_field BOOLEAN, second_field INTEGER);
+            INSERT INTO t VALUES (1, 42), (0, 0);
+        ",
+        "SELECT first_field, second_field FROM t",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            OneFieldStruct {
+                left: Left { first_field: true },
+                right: Right { second_field: 42 },
+            },
+            OneFieldStruct {
+                left: Left { first_field: false },
+                right: Right { second_field: 0 },
+            },
+        ]
+    );
+}
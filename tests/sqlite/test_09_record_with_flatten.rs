@@ -0,0 +1,19 @@
+use crate::fetch_all;
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn int_columns_into_struct_with_flattenned_field() {
+    #[derive(Debug, ::serde::Deserialize, PartialEq, Eq)]
+    struct Record {
+        one: i32,
+        #[serde(flatten)]
+        the_rest: HashMap<String, i32>,
+    }
+    let rows: Vec<Record> = fetch_all("SELECT 1 AS one, 2 AS two, 3 AS three")
+        .await
+        .unwrap();
+    let the_rest: HashMap<String, i32> = vec![("two".to_owned(), 2), ("three".to_owned(), 3)]
+        .into_iter()
+        .collect();
+    assert_eq!(rows, vec![Record { one: 1, the_rest }]);
+}
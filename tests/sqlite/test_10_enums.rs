@@ -0,0 +1,86 @@
+use serde::Deserialize;
+
+use crate::fetch_one;
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    Red,
+    Green,
+    Magenta,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Data {
+    color: Color,
+}
+
+#[tokio::test]
+async fn test_enum_by_itself() {
+    let color: Color = fetch_one("SELECT 'red'").await.unwrap();
+
+    assert_eq!(color, Color::Red);
+}
+
+#[tokio::test]
+async fn test_enum_inside_struct() {
+    let data: Data = fetch_one("SELECT 'magenta' AS color").await.unwrap();
+
+    assert_eq!(
+        data,
+        Data {
+            color: Color::Magenta
+        }
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Shape {
+    Circle { radius: f64 },
+    Square { side: i64 },
+}
+
+#[tokio::test]
+async fn test_internally_tagged_enum_from_columns() {
+    let shapes: Vec<Shape> = crate::fetch_all(
+        "SELECT 'circle' AS kind, 1.5 AS radius, NULL AS side
+         UNION ALL SELECT 'square', NULL, 3",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        shapes,
+        [Shape::Circle { radius: 1.5 }, Shape::Square { side: 3 }]
+    );
+}
+
+#[tokio::test]
+async fn test_enum_from_an_integer_code() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Status {
+        #[serde(rename = "1")]
+        Open,
+        #[serde(rename = "2")]
+        Closed,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Ticket {
+        color: Color,
+        status: Status,
+    }
+
+    let ticket: Ticket = fetch_one("SELECT 2 AS color, 2 AS status").await.unwrap();
+    assert_eq!(
+        ticket,
+        Ticket {
+            color: Color::Magenta,
+            status: Status::Closed,
+        }
+    );
+
+    let err = fetch_one::<Status>("SELECT 3").await.unwrap_err();
+    assert!(err.to_string().contains("unknown variant `3`"), "{err}");
+}
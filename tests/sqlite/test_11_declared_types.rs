@@ -0,0 +1,81 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::Deserialize;
+
+use crate::{fetch_all_with_schema, fetch_one_with_schema};
+
+const SCHEMA: &str = "
+    CREATE TABLE events (
+        day DATE,
+        at TIME,
+        created DATETIME
+    );
+    INSERT INTO events VALUES ('2017-11-16', '14:45:12.012345', '2017-11-16 14:45:12.012345');
+";
+
+const TEST_DATE: NaiveDate = NaiveDate::from_ymd_opt(2017, 11, 16).unwrap();
+const TEST_TIME: NaiveTime = NaiveTime::from_hms_micro_opt(14, 45, 12, 12_345).unwrap();
+
+#[tokio::test]
+async fn test_chrono_date() {
+    let date: NaiveDate = fetch_one_with_schema(SCHEMA, "SELECT day FROM events")
+        .await
+        .unwrap();
+
+    assert_eq!(date, TEST_DATE);
+}
+
+#[tokio::test]
+async fn test_chrono_time() {
+    let time: NaiveTime = fetch_one_with_schema(SCHEMA, "SELECT at FROM events")
+        .await
+        .unwrap();
+
+    assert_eq!(time, TEST_TIME);
+}
+
+#[tokio::test]
+async fn test_chrono_datetime() {
+    let datetime: NaiveDateTime = fetch_one_with_schema(SCHEMA, "SELECT created FROM events")
+        .await
+        .unwrap();
+
+    assert_eq!(datetime, TEST_DATE.and_time(TEST_TIME));
+}
+
+#[tokio::test]
+async fn test_chrono_datetime_from_unix_epoch() {
+    let rows: Vec<NaiveDateTime> = fetch_all_with_schema(
+        "
+            CREATE TABLE events (created DATETIME);
+            INSERT INTO events VALUES (1510843512);
+        ",
+        "SELECT created FROM events",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(rows, [TEST_DATE.and_hms_opt(14, 45, 12).unwrap()]);
+}
+
+#[tokio::test]
+async fn test_chrono_struct() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Event {
+        day: NaiveDate,
+        at: NaiveTime,
+        created: Option<NaiveDateTime>,
+    }
+
+    let event: Event = fetch_one_with_schema(SCHEMA, "SELECT * FROM events")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        event,
+        Event {
+            day: TEST_DATE,
+            at: TEST_TIME,
+            created: Some(TEST_DATE.and_time(TEST_TIME)),
+        }
+    );
+}
@@ -0,0 +1,60 @@
+use crate::{fetch_one, fetch_one_with_schema};
+
+#[derive(Debug, serde::Deserialize, PartialEq, Eq)]
+struct Bytes(#[serde(with = "serde_bytes_compat")] Vec<u8>);
+
+/// `Vec<u8>` deserializes from a sequence by default, this accepts the bytes directly
+mod serde_bytes_compat {
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+        struct BytesVisitor;
+
+        impl serde::de::Visitor<'_> for BytesVisitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("bytes")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(v.to_vec())
+            }
+        }
+
+        d.deserialize_bytes(BytesVisitor)
+    }
+}
+
+#[tokio::test]
+async fn blob_literal_as_bytes() {
+    let row: Bytes = fetch_one("SELECT x'DEADBEEF'").await.unwrap();
+    assert_eq!(row, Bytes(vec![0xDE, 0xAD, 0xBE, 0xEF]));
+}
+
+#[tokio::test]
+async fn blob_column_as_bytes() {
+    let row: Bytes = fetch_one_with_schema(
+        "
+            CREATE TABLE files (content BLOB);
+            INSERT INTO files VALUES (x'0102');
+        ",
+        "SELECT content FROM files",
+    )
+    .await
+    .unwrap();
+    assert_eq!(row, Bytes(vec![1, 2]));
+}
+
+#[tokio::test]
+async fn mixed_storage_classes_into_json_value() {
+    // A column without a declared type can hold a different storage class on every row
+    let row: (serde_json::Value, serde_json::Value, serde_json::Value) =
+        fetch_one("SELECT 1, 1.5, 'text'").await.unwrap();
+    assert_eq!(
+        row,
+        (
+            serde_json::json!(1),
+            serde_json::json!(1.5),
+            serde_json::json!("text")
+        )
+    );
+}
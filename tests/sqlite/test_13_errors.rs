@@ -0,0 +1,52 @@
+use serde::Deserialize;
+use serde_sqlx::Error;
+
+use crate::{fetch_one, fetch_one_with_schema};
+
+#[derive(Debug, Deserialize)]
+#[allow(unused)]
+struct User {
+    id: i64,
+    name: String,
+}
+
+fn unwrap_error<T: std::fmt::Debug>(res: anyhow::Result<T>) -> Error {
+    res.unwrap_err().downcast::<Error>().unwrap()
+}
+
+#[tokio::test]
+async fn missing_column() {
+    let err = unwrap_error(fetch_one::<User>("SELECT 1 AS id").await);
+
+    assert!(matches!(err, Error::MissingColumn { .. }), "{err:?}");
+    assert_eq!(err.column(), Some("name"));
+}
+
+#[tokio::test]
+async fn unexpected_null_in_table_column() {
+    let err = unwrap_error(
+        fetch_one_with_schema::<User>(
+            "
+                CREATE TABLE users (id INTEGER, name TEXT);
+                INSERT INTO users VALUES (1, NULL);
+            ",
+            "SELECT id, name FROM users",
+        )
+        .await,
+    );
+
+    assert!(matches!(err, Error::UnexpectedNull { .. }), "{err:?}");
+    assert_eq!(err.column(), Some("name"));
+    assert_eq!(err.index(), Some(1));
+    assert_eq!(err.sql_type(), Some("TEXT"));
+}
+
+#[tokio::test]
+async fn type_mismatch_in_field() {
+    let err = unwrap_error(fetch_one::<User>("SELECT 'one' AS id, 'name' AS name").await);
+
+    assert!(matches!(err, Error::TypeMismatch { .. }), "{err:?}");
+    assert_eq!(err.column(), Some("id"));
+    assert_eq!(err.index(), Some(0));
+    assert_eq!(err.rust_type(), Some("i64"));
+}
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+use serde_sqlx::Serde;
+
+use crate::conn;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: i64,
+    name: String,
+    active: bool,
+}
+
+#[tokio::test]
+async fn query_as_fetch_all() {
+    let mut conn = conn(
+        "
+            CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, active BOOLEAN);
+            INSERT INTO users VALUES (1, 'alice', 1), (2, 'bob', 0);
+        ",
+    )
+    .await;
+
+    let users: Vec<Serde<User>> = sqlx::query_as("SELECT * FROM users ORDER BY id")
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+    let users: Vec<User> = users.into_iter().map(Serde::into_inner).collect();
+
+    assert_eq!(
+        users,
+        vec![
+            User {
+                id: 1,
+                name: "alice".to_owned(),
+                active: true,
+            },
+            User {
+                id: 2,
+                name: "bob".to_owned(),
+                active: false,
+            },
+        ]
+    );
+}
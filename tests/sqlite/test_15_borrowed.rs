@@ -0,0 +1,49 @@
+use std::borrow::Cow;
+
+use crate::conn;
+
+#[derive(Debug, serde::Deserialize, PartialEq)]
+struct File<'a> {
+    name: &'a str,
+    #[serde(borrow)]
+    mime: Cow<'a, str>,
+    content: &'a [u8],
+}
+
+#[tokio::test]
+async fn struct_with_borrowed_fields() {
+    let mut conn = conn(
+        "
+            CREATE TABLE files (name TEXT, mime TEXT, content BLOB);
+            INSERT INTO files VALUES ('a.bin', 'application/octet-stream', x'0102');
+        ",
+    )
+    .await;
+    let row = sqlx::query("SELECT name, mime, content FROM files")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    let file: File = serde_sqlx::from_row_ref::<sqlx::Sqlite, _>(&row).unwrap();
+    assert_eq!(
+        file,
+        File {
+            name: "a.bin",
+            mime: Cow::Borrowed("application/octet-stream"),
+            content: &[1, 2],
+        }
+    );
+    assert!(matches!(file.mime, Cow::Borrowed(_)));
+}
+
+#[tokio::test]
+async fn literal_as_borrowed_str() {
+    let mut conn = conn("").await;
+    let row = sqlx::query("SELECT 'abc'")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    let value: &str = serde_sqlx::from_row_ref::<sqlx::Sqlite, _>(&row).unwrap();
+    assert_eq!(value, "abc");
+}
@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use serde_sqlx::{DeserializeOptions, RenameRule};
+
+use crate::conn;
+
+async fn keys(query: &str, options: &DeserializeOptions) -> Vec<String> {
+    let row = sqlx::query(query)
+        .fetch_one(&mut conn("").await)
+        .await
+        .unwrap();
+
+    let map: BTreeMap<String, i64> =
+        serde_sqlx::from_row_with::<sqlx::Sqlite, _>(row, options).unwrap();
+    map.into_keys().collect()
+}
+
+const QUERY: &str = r#"
+    SELECT 1 AS "user_id", 2 AS "UserName", 3 AS "HTTPStatus", 4 AS "address2_line",
+        5 AS "created-at", 6 AS "id"
+"#;
+
+#[tokio::test]
+async fn snake_case_rule() {
+    let options = DeserializeOptions::new().rename_columns(RenameRule::SnakeCase);
+    let mut expected = [
+        "user_id",
+        "user_name",
+        "http_status",
+        "address2_line",
+        "created_at",
+        "id",
+    ];
+    expected.sort();
+
+    assert_eq!(keys(QUERY, &options).await, expected);
+}
+
+#[tokio::test]
+async fn camel_case_rule() {
+    let options = DeserializeOptions::new().rename_columns(RenameRule::CamelCase);
+    let mut expected = [
+        "userId",
+        "userName",
+        "httpStatus",
+        "address2Line",
+        "createdAt",
+        "id",
+    ];
+    expected.sort();
+
+    assert_eq!(keys(QUERY, &options).await, expected);
+}
+
+#[tokio::test]
+async fn names_are_kept_by_default() {
+    let mut expected = [
+        "user_id",
+        "UserName",
+        "HTTPStatus",
+        "address2_line",
+        "created-at",
+        "id",
+    ];
+    expected.sort();
+
+    assert_eq!(keys(QUERY, &DeserializeOptions::new()).await, expected);
+}